@@ -0,0 +1,117 @@
+use std::fmt;
+
+use crate::util::json_string;
+
+/// The answer to one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Integer(i64),
+    BigUnsigned(u128),
+    Text(String),
+    /// Multi-line output, one string per row.
+    Grid(Vec<String>),
+}
+
+impl Answer {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Answer::Integer(_) => "integer",
+            Answer::BigUnsigned(_) => "big_unsigned",
+            Answer::Text(_) => "text",
+            Answer::Grid(_) => "grid",
+        }
+    }
+
+    /// Serializes the answer as a JSON value. Integers are written as JSON
+    /// numbers, text as a string and grids as an array of row strings.
+    /// Big unsigned answers are written as strings of digits, since many
+    /// JSON readers turn numbers into doubles, which can't hold every
+    /// integer above 2^53.
+    pub fn to_json(&self) -> String {
+        match self {
+            Answer::Integer(value) => value.to_string(),
+            Answer::BigUnsigned(value) => json_string(&value.to_string()),
+            Answer::Text(text) => json_string(text),
+            Answer::Grid(rows) => format!(
+                "[{}]",
+                rows.iter()
+                    .map(|row| json_string(row))
+                    .collect::<Vec<String>>()
+                    .join(",")
+            ),
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Integer(value) => write!(f, "{}", value),
+            Answer::BigUnsigned(value) => write!(f, "{}", value),
+            Answer::Text(text) => write!(f, "{}", text),
+            Answer::Grid(rows) => write!(f, "{}", rows.join("\n")),
+        }
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Answer {
+        Answer::Integer(value)
+    }
+}
+
+impl From<u128> for Answer {
+    fn from(value: u128) -> Answer {
+        Answer::BigUnsigned(value)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Answer {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Answer {
+        Answer::Text(text.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Answer::Integer(-12).to_string(), "-12");
+        assert_eq!(
+            Answer::BigUnsigned(u128::MAX).to_string(),
+            "340282366920938463463374607431768211455"
+        );
+        assert_eq!(Answer::from("ZUJUAFHP").to_string(), "ZUJUAFHP");
+        assert_eq!(
+            Answer::Grid(vec!["#.".to_string(), ".#".to_string()]).to_string(),
+            "#.\n.#"
+        );
+    }
+
+    #[test]
+    fn test_to_json() {
+        assert_eq!(Answer::Integer(58412).to_json(), "58412");
+        assert_eq!(
+            Answer::BigUnsigned(539051801941).to_json(),
+            "\"539051801941\""
+        );
+        // 2^53 + 1, which a double would round to 2^53.
+        assert_eq!(
+            Answer::BigUnsigned(9007199254740993).to_json(),
+            "\"9007199254740993\""
+        );
+        assert_eq!(Answer::from("say \"hi\"").to_json(), "\"say \\\"hi\\\"\"");
+        assert_eq!(
+            Answer::Grid(vec!["#.".to_string(), ".#".to_string()]).to_json(),
+            "[\"#.\",\".#\"]"
+        );
+    }
+}
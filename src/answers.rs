@@ -0,0 +1,284 @@
+use std::path::{Path, PathBuf};
+
+use crate::{
+    error::AocError,
+    util::{column_of, read_file},
+};
+
+/// The answers file `verify` reads when none is given.
+pub const DEFAULT_ANSWERS_FILE: &str = "answers.toml";
+
+/// The known answers for one day's input. A part's answer is `None` when it
+/// isn't known yet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KnownAnswers {
+    pub day: u8,
+    pub input: PathBuf,
+    pub parts: [Option<String>; 2],
+}
+
+/// Reads an answers file. Input paths in it are relative to the file's own
+/// directory, so it can be used from anywhere.
+pub fn load(path: impl AsRef<Path>) -> Result<Vec<KnownAnswers>, AocError> {
+    let path = path.as_ref();
+    let base = path.parent().unwrap_or(Path::new(""));
+    Ok(parse(&read_file(path)?)?
+        .into_iter()
+        .map(|answers| KnownAnswers {
+            input: base.join(&answers.input),
+            ..answers
+        })
+        .collect())
+}
+
+/// Parses the subset of TOML that answers files use: comments, and an
+/// `[[answer]]` table per input, holding `day` and `input` and optionally
+/// `part1` and `part2`. Answers can be integers or strings.
+///
+/// ```toml
+/// [[answer]]
+/// day = 13
+/// input = "inputs/problem_13.txt"
+/// part1 = 716
+/// part2 = "RPCKFBLR"
+/// ```
+pub fn parse(text: &str) -> Result<Vec<KnownAnswers>, AocError> {
+    let mut entries: Vec<Entry> = Vec::new();
+    for (index, raw_line) in text.lines().enumerate() {
+        let line = strip_comment(raw_line).trim();
+        if line.is_empty() {
+            continue;
+        }
+        if line == "[[answer]]" {
+            entries.push((index, None, None, [None, None]));
+            continue;
+        }
+        let error = |column: usize, message: &str| {
+            Err(AocError::parse(index + 1, column, message.to_string()))
+        };
+        if line.starts_with('[') {
+            return error(1, "expected '[[answer]]'");
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            return error(1, "expected 'key = value'");
+        };
+        let (key, value) = (key.trim(), value.trim());
+        let Some((_, day, input, parts)) = entries.last_mut() else {
+            return error(1, "expected '[[answer]]' before any keys");
+        };
+        let value_column = column_of(raw_line, value);
+        let duplicate = match key {
+            "day" => day
+                .replace(match value.parse::<u8>() {
+                    Ok(value) if (1..=25).contains(&value) => value,
+                    _ => return error(value_column, "expected a day from 1 to 25"),
+                })
+                .is_some(),
+            "input" => input
+                .replace(PathBuf::from(parse_string(index, value_column, value)?))
+                .is_some(),
+            "part1" | "part2" => parts[(key == "part2") as usize]
+                .replace(parse_answer(index, value_column, value)?)
+                .is_some(),
+            _ => {
+                return error(
+                    1,
+                    &format!("unknown key '{}', expected day, input, part1 or part2", key),
+                )
+            }
+        };
+        if duplicate {
+            return error(1, &format!("'{}' is set twice", key));
+        }
+    }
+
+    entries
+        .into_iter()
+        .map(|(index, day, input, parts)| match (day, input) {
+            (Some(day), Some(input)) => Ok(KnownAnswers { day, input, parts }),
+            _ => Err(AocError::parse(
+                index + 1,
+                1,
+                "every [[answer]] needs a day and an input",
+            )),
+        })
+        .collect()
+}
+
+/// Parses the answers for a single input, as kept next to it in a sidecar
+/// file: just the `part1` and `part2` keys of an `[[answer]]` table, either
+/// of which can be left out.
+///
+/// ```toml
+/// part1 = 17
+/// ```
+pub fn parse_parts(text: &str) -> Result<[Option<String>; 2], AocError> {
+    let mut parts = [None, None];
+    for (index, raw_line) in text.lines().enumerate() {
+        let line = strip_comment(raw_line).trim();
+        if line.is_empty() {
+            continue;
+        }
+        let error = |message: String| Err(AocError::parse(index + 1, 1, message));
+        let Some((key, value)) = line.split_once('=') else {
+            return error("expected 'key = value'".to_string());
+        };
+        let (key, value) = (key.trim(), value.trim());
+        let part = match key {
+            "part1" => 0,
+            "part2" => 1,
+            _ => return error(format!("unknown key '{}', expected part1 or part2", key)),
+        };
+        let answer = parse_answer(index, column_of(raw_line, value), value)?;
+        if parts[part].replace(answer).is_some() {
+            return error(format!("'{}' is set twice", key));
+        }
+    }
+    Ok(parts)
+}
+
+/// An `[[answer]]` table being read: the index of its header line, then the
+/// day, input and answers set so far.
+type Entry = (usize, Option<u8>, Option<PathBuf>, [Option<String>; 2]);
+
+/// Drops a trailing `# comment`, leaving any `#` inside a string alone.
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut escaped = false;
+    for (offset, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..offset],
+            _ => {}
+        }
+    }
+    line
+}
+
+/// Parses a part's answer, which is either a string or an integer.
+fn parse_answer(index: usize, column: usize, value: &str) -> Result<String, AocError> {
+    match value.starts_with('"') {
+        true => parse_string(index, column, value),
+        false => parse_integer(index, column, value),
+    }
+}
+
+/// Parses a basic `"quoted"` string, with `\"`, `\\`, `\n` and `\t` escapes.
+fn parse_string(index: usize, column: usize, value: &str) -> Result<String, AocError> {
+    let error = |message: &str| Err(AocError::parse(index + 1, column, message.to_string()));
+    let Some(inner) = value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .filter(|_| value.len() >= 2)
+    else {
+        return error("expected a quoted string");
+    };
+    let mut string = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('"') => string.push('"'),
+                Some('\\') => string.push('\\'),
+                Some('n') => string.push('\n'),
+                Some('t') => string.push('\t'),
+                _ => return error("unsupported escape in string"),
+            },
+            '"' => return error("unescaped '\"' in string"),
+            c => string.push(c),
+        }
+    }
+    Ok(string)
+}
+
+/// Parses an integer, allowing `_` between digits like TOML does, and
+/// returns it the way an `Answer` would print it.
+fn parse_integer(index: usize, column: usize, value: &str) -> Result<String, AocError> {
+    let digits = value.replace('_', "");
+    match digits.parse::<i128>() {
+        Ok(number) if !value.starts_with('_') && !value.ends_with('_') => Ok(number.to_string()),
+        _ => Err(AocError::parse(
+            index + 1,
+            column,
+            format!("expected an integer or a quoted string, found '{}'", value),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let answers = parse(
+            "# Known answers\n\
+             [[answer]]\n\
+             day = 4\n\
+             input = \"inputs/problem_04.txt\" # the bundled one\n\
+             part1 = 58_412\n\
+             \n\
+             [[answer]]\n\
+             day = 13\n\
+             input = \"friend's #13.txt\"\n\
+             part2 = \"RPCK\\\"FBLR\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            answers,
+            vec![
+                KnownAnswers {
+                    day: 4,
+                    input: PathBuf::from("inputs/problem_04.txt"),
+                    parts: [Some("58412".to_string()), None],
+                },
+                KnownAnswers {
+                    day: 13,
+                    input: PathBuf::from("friend's #13.txt"),
+                    parts: [None, Some("RPCK\"FBLR".to_string())],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_answers() {
+        match parse("[[answer]]\nday = 4\ninput = \"a.txt\"\npart1 = 12x") {
+            Err(AocError::Parse { line, column, .. }) => assert_eq!((line, column), (4, 9)),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+        assert!(parse("day = 4").is_err());
+        assert!(parse("[answers]").is_err());
+        assert!(parse("[[answer]]\nday = 26\ninput = \"a.txt\"").is_err());
+        assert!(parse("[[answer]]\nday = 4").is_err());
+        assert!(parse("[[answer]]\nday = 4\ninput = \"a.txt\"\nday = 5").is_err());
+        assert!(parse("[[answer]]\nday = 4\ninput = a.txt").is_err());
+        assert!(parse("[[answer]]\nday = 4\ninput = \"a.txt\"\npart3 = 1").is_err());
+    }
+
+    #[test]
+    fn test_parse_parts() {
+        assert_eq!(
+            parse_parts("# Only part 2 is known\npart2 = \"Merry Christmas!\"\n").unwrap(),
+            [None, Some("Merry Christmas!".to_string())]
+        );
+        assert_eq!(parse_parts("").unwrap(), [None, None]);
+        match parse_parts("part1 = 1\npart1 = 2") {
+            Err(AocError::Parse { line, .. }) => assert_eq!(line, 2),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+        assert!(parse_parts("day = 4").is_err());
+    }
+
+    #[test]
+    fn test_load_resolves_inputs_next_to_the_file() {
+        let answers = load(DEFAULT_ANSWERS_FILE).unwrap();
+        assert!(answers.iter().all(|answers| answers.input.is_file()));
+        assert!(matches!(
+            load("./missing/answers.toml"),
+            Err(AocError::MissingInput { .. })
+        ));
+    }
+}
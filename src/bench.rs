@@ -0,0 +1,195 @@
+use std::time::{Duration, Instant};
+
+use crate::{cli::Selection, error::AocError, input::InputSource, problem::Solver};
+
+/// Summary statistics over repeated timings of the same work.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Stats {
+        if samples.is_empty() {
+            return Stats {
+                min: Duration::ZERO,
+                median: Duration::ZERO,
+                mean: Duration::ZERO,
+                stddev: Duration::ZERO,
+            };
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let middle = sorted.len() / 2;
+        let median = match sorted.len() % 2 {
+            0 => (sorted[middle - 1] + sorted[middle]) / 2,
+            _ => sorted[middle],
+        };
+
+        let seconds: Vec<f64> = samples.iter().map(|d| d.as_secs_f64()).collect();
+        let mean = seconds.iter().sum::<f64>() / seconds.len() as f64;
+        // Sample standard deviation, since the runs are a sample of how
+        // long the solution could take.
+        let variance = match seconds.len() {
+            1 => 0.0,
+            n => seconds.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (n - 1) as f64,
+        };
+
+        Stats {
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+/// Timings for one part of one day. Input is read and parsed once per day,
+/// so `load` and `parse` are shared by both parts of a day.
+pub struct BenchResult {
+    pub day: u8,
+    pub name: String,
+    pub part: u8,
+    /// How long it took to read the day's input, which is done once.
+    pub load: Duration,
+    pub parse: Result<Stats, String>,
+    pub solve: Result<Stats, String>,
+}
+
+/// Runs `work` `warmup` times untimed, then `iterations` times timed. The
+/// value from the last run is returned along with the timings.
+fn time<T>(
+    iterations: usize,
+    warmup: usize,
+    mut work: impl FnMut() -> Result<T, AocError>,
+) -> Result<(Stats, T), AocError> {
+    for _ in 0..warmup {
+        work()?;
+    }
+    let mut samples = Vec::with_capacity(iterations);
+    let mut value = None;
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let output = work()?;
+        samples.push(start.elapsed());
+        // Dropping the previous run's value, e.g. a large parsed input,
+        // mustn't count towards this run's time.
+        value = Some(output);
+    }
+    let value = value.expect("at least one iteration");
+    Ok((Stats::from_samples(&samples), value))
+}
+
+/// Times parsing each selected day and solving each selected part
+/// `iterations` times after `warmup` untimed runs. Input is read once per
+/// day, outside of the timed runs, and every part is solved from the same
+/// parsed input.
+pub fn bench(
+    problems: &[Box<dyn Solver>],
+    selection: &Selection,
+    source: &InputSource,
+    iterations: usize,
+    warmup: usize,
+) -> Vec<BenchResult> {
+    let mut results = Vec::new();
+    problems
+        .iter()
+        .filter(|problem| selection.includes_day(problem.day()))
+        .for_each(|problem| {
+            let load_start = Instant::now();
+            let input = source.load(problem.as_ref());
+            let load = load_start.elapsed();
+
+            let parsed =
+                input.and_then(|input| time(iterations, warmup, || problem.parse_input(&input)));
+
+            for part in [1, 2]
+                .into_iter()
+                .filter(|part| selection.includes_part(*part))
+            {
+                let (parse, solve) = match &parsed {
+                    Ok((parse_stats, parsed)) => (
+                        Ok(parse_stats.clone()),
+                        time(iterations, warmup, || problem.solve_part(parsed, part))
+                            .map(|(stats, _)| stats)
+                            .map_err(|e| e.to_string()),
+                    ),
+                    Err(error) => (Err(error.to_string()), Err(error.to_string())),
+                };
+                results.push(BenchResult {
+                    day: problem.day(),
+                    name: problem.name().to_string(),
+                    part,
+                    load,
+                    parse,
+                    solve,
+                });
+            }
+        });
+    results
+}
+
+fn render_stats(label: &str, stats: &Result<Stats, String>) -> String {
+    match stats {
+        Ok(stats) => format!(
+            " - {}: min {:.2?}, median {:.2?}, mean {:.2?}, stddev {:.2?}",
+            label, stats.min, stats.median, stats.mean, stats.stddev
+        ),
+        Err(error) => format!(" - {}: error: {}", label, error),
+    }
+}
+
+pub fn render(results: &[BenchResult], iterations: usize, warmup: usize) -> String {
+    let mut output = vec![format!(
+        "~ Advent of Code 2021 ~ ({} iterations, {} warm-up)",
+        iterations, warmup
+    )];
+    let mut previous_day = None;
+    for result in results {
+        if previous_day != Some(result.day) {
+            output.push(result.name.clone());
+            output.push(format!(" - Input: {:.2?}", result.load));
+            // A failed load or parse is already reported against each part.
+            if result.parse.is_ok() {
+                output.push(render_stats("Parse", &result.parse));
+            }
+            previous_day = Some(result.day);
+        }
+        output.push(render_stats(
+            &format!("Part {}", result.part),
+            &result.solve,
+        ));
+    }
+    output.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|ms| Duration::from_millis(*ms)).collect()
+    }
+
+    #[test]
+    fn test_stats_from_samples() {
+        let stats = Stats::from_samples(&millis(&[4, 2, 8, 6]));
+        assert_eq!(stats.min, Duration::from_millis(2));
+        assert_eq!(stats.median, Duration::from_millis(5));
+        assert_eq!(stats.mean, Duration::from_millis(5));
+        // sqrt(((1 + 9 + 9 + 1) * 1ms^2) / 3)
+        assert_eq!(stats.stddev.as_micros(), 2581);
+    }
+
+    #[test]
+    fn test_stats_single_and_empty_samples() {
+        let stats = Stats::from_samples(&millis(&[7]));
+        assert_eq!(stats.median, Duration::from_millis(7));
+        assert_eq!(stats.stddev, Duration::ZERO);
+        assert_eq!(Stats::from_samples(&[]).mean, Duration::ZERO);
+    }
+}
@@ -0,0 +1,501 @@
+use crate::{answers::DEFAULT_ANSWERS_FILE, report::OutputFormat};
+
+pub const USAGE: &str = "Usage:
+    advent-of-code-2021 [run] [--day N]... [--days A-B[,C...]] [--part 1|2]
+                        [--input FILE|-] [--input-dir DIR] [--format text|json|csv]
+                        [--jobs N] [--show-moves]
+    advent-of-code-2021 bench [--day N]... [--days A-B[,C...]] [--part 1|2]
+                        [--input FILE|-] [--input-dir DIR]
+                        [--iterations N] [--warmup N]
+    advent-of-code-2021 verify [--day N]... [--days A-B[,C...]] [--part 1|2]
+                        [--answers FILE] [--jobs N]
+    advent-of-code-2021 new --day N --title TITLE
+    advent-of-code-2021 list
+    advent-of-code-2021 help
+
+Running with no arguments solves every day. `--input` reads a single day's
+input from FILE, or from stdin when given `-`. `--input-dir` (or the
+AOC_INPUT_DIR environment variable) points at a directory of problem_XX.txt
+files. Days without an input there use the input bundled with the binary.
+`--format` picks between the human readable report (the default) and
+per-part JSON or CSV records. `--jobs` solves days and parts on up to N
+threads, or one per CPU when N is 0; the output order doesn't change.
+`--show-moves` also prints every step of Day 23's cheapest solution to
+stderr, when Day 23 is selected.
+
+`bench` solves each selected part `--iterations` times (default 10) after
+`--warmup` untimed runs (default 2) and reports min/median/mean/stddev of
+the solve time. Reading the input is timed once per day, separately.
+
+`verify` solves every input listed in the answers file (default
+answers.toml) for the selected days and reports PASS, FAIL or UNKNOWN for
+each part, with the difference for every failure. Days without known
+answers are solved from their bundled input and reported as UNKNOWN. It
+exits with status 1 if anything failed.
+
+`new` starts a day that isn't registered yet: it writes src/problem_XX.rs
+from the usual skeleton, an empty input and example with an answers file,
+and adds the day to the `days!` list in src/lib.rs. Run it from the crate's
+root.";
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run(RunOptions),
+    Bench(BenchOptions),
+    Verify(VerifyOptions),
+    New { day: u8, title: String },
+    List,
+    Help,
+}
+
+/// Which days and parts a command should cover. An empty `days` list means
+/// every day, and a missing `part` means both parts.
+#[derive(Debug, Default, PartialEq)]
+pub struct Selection {
+    pub days: Vec<u8>,
+    pub part: Option<u8>,
+}
+
+impl Selection {
+    pub fn includes_day(&self, day: u8) -> bool {
+        self.days.is_empty() || self.days.contains(&day)
+    }
+
+    pub fn includes_part(&self, part: u8) -> bool {
+        self.part.is_none() || self.part == Some(part)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RunOptions {
+    pub selection: Selection,
+    pub input: Option<String>,
+    pub input_dir: Option<String>,
+    pub format: OutputFormat,
+    pub jobs: usize,
+    pub show_moves: bool,
+}
+
+impl Default for RunOptions {
+    fn default() -> RunOptions {
+        RunOptions {
+            selection: Selection::default(),
+            input: None,
+            input_dir: None,
+            format: OutputFormat::default(),
+            jobs: 1,
+            show_moves: false,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BenchOptions {
+    pub run: RunOptions,
+    pub iterations: usize,
+    pub warmup: usize,
+}
+
+impl Default for BenchOptions {
+    fn default() -> BenchOptions {
+        BenchOptions {
+            run: RunOptions::default(),
+            iterations: 10,
+            warmup: 2,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct VerifyOptions {
+    pub run: RunOptions,
+    pub answers: String,
+}
+
+impl Default for VerifyOptions {
+    fn default() -> VerifyOptions {
+        VerifyOptions {
+            run: RunOptions::default(),
+            answers: DEFAULT_ANSWERS_FILE.to_string(),
+        }
+    }
+}
+
+/// The command whose flags are being parsed, holding the options only that
+/// command takes.
+enum Extras<'a> {
+    Run,
+    Bench(&'a mut BenchOptions),
+    Verify(&'a mut VerifyOptions),
+}
+
+fn parse_day(value: &str) -> Result<u8, String> {
+    match value.trim().parse::<u8>() {
+        Ok(day) if (1..=25).contains(&day) => Ok(day),
+        _ => Err(format!("invalid day '{}', expected 1-25", value)),
+    }
+}
+
+/// Parses a comma separated list of days and inclusive ranges, e.g.
+/// `1,3,5-9`.
+fn parse_days(value: &str) -> Result<Vec<u8>, String> {
+    let mut days = Vec::new();
+    for chunk in value.split(',') {
+        match chunk.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (parse_day(start)?, parse_day(end)?);
+                if start > end {
+                    return Err(format!("invalid day range '{}'", chunk));
+                }
+                days.extend(start..=end);
+            }
+            None => days.push(parse_day(chunk)?),
+        }
+    }
+    Ok(days)
+}
+
+fn parse_part(value: &str) -> Result<u8, String> {
+    match value {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err(format!("invalid part '{}', expected 1 or 2", value)),
+    }
+}
+
+fn parse_count(flag: &str, value: &str, min: usize) -> Result<usize, String> {
+    match value.parse::<usize>() {
+        Ok(count) if count >= min => Ok(count),
+        _ => Err(format!(
+            "invalid value '{}' for '{}', expected a number >= {}",
+            value, flag, min
+        )),
+    }
+}
+
+/// Parses the flags shared by `run`, `bench` and `verify`, plus the ones
+/// only `extras`' command takes. `bench` doesn't take `--format` or
+/// `--jobs`, since timings taken side by side would skew each other, and
+/// `verify` reads its inputs from the answers file instead of `--input`.
+fn parse_run_options(args: &[String], mut extras: Extras) -> Result<RunOptions, String> {
+    let mut options = RunOptions::default();
+    let selection = &mut options.selection;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) => (flag, Some(value.to_string())),
+            None => (arg.as_str(), None),
+        };
+        let mut value = || match inline_value.clone() {
+            Some(value) => Ok(value),
+            None => args
+                .next()
+                .cloned()
+                .ok_or_else(|| format!("missing value for '{}'", flag)),
+        };
+        match (flag, &mut extras) {
+            ("-d" | "--day", _) => selection.days.push(parse_day(&value()?)?),
+            ("--days", _) => selection.days.extend(parse_days(&value()?)?),
+            ("-p" | "--part", _) => selection.part = Some(parse_part(&value()?)?),
+            ("-i" | "--input", Extras::Run | Extras::Bench(_)) => options.input = Some(value()?),
+            ("--input-dir", Extras::Run | Extras::Bench(_)) => options.input_dir = Some(value()?),
+            ("-f" | "--format", Extras::Run) => options.format = value()?.parse()?,
+            ("--show-moves", Extras::Run) if inline_value.is_none() => options.show_moves = true,
+            ("-j" | "--jobs", Extras::Run | Extras::Verify(_)) => {
+                options.jobs = parse_count(flag, &value()?, 0)?
+            }
+            ("-n" | "--iterations", Extras::Bench(bench)) => {
+                bench.iterations = parse_count(flag, &value()?, 1)?
+            }
+            ("-w" | "--warmup", Extras::Bench(bench)) => {
+                bench.warmup = parse_count(flag, &value()?, 0)?
+            }
+            ("-a" | "--answers", Extras::Verify(verify)) => verify.answers = value()?,
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+    selection.days.sort_unstable();
+    selection.days.dedup();
+    if options.input.is_some() && options.selection.days.len() != 1 {
+        return Err("--input needs exactly one day selected with --day".to_string());
+    }
+    Ok(options)
+}
+
+fn parse_bench_options(args: &[String]) -> Result<BenchOptions, String> {
+    let mut options = BenchOptions::default();
+    options.run = parse_run_options(args, Extras::Bench(&mut options))?;
+    Ok(options)
+}
+
+fn parse_new_options(args: &[String]) -> Result<Command, String> {
+    let (mut day, mut title) = (None, None);
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) => (flag, Some(value.to_string())),
+            None => (arg.as_str(), None),
+        };
+        let value = inline_value
+            .or_else(|| args.next().cloned())
+            .ok_or_else(|| format!("missing value for '{}'", flag))?;
+        match flag {
+            "-d" | "--day" => day = Some(parse_day(&value)?),
+            "-t" | "--title" => title = Some(value),
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+    match (day, title) {
+        (Some(day), Some(title)) if !title.trim().is_empty() && !title.contains('\n') => {
+            Ok(Command::New {
+                day,
+                title: title.trim().to_string(),
+            })
+        }
+        (Some(_), Some(_)) => Err("--title needs a single line of text".to_string()),
+        _ => Err("new needs both --day and --title".to_string()),
+    }
+}
+
+fn parse_verify_options(args: &[String]) -> Result<VerifyOptions, String> {
+    let mut options = VerifyOptions::default();
+    options.run = parse_run_options(args, Extras::Verify(&mut options))?;
+    Ok(options)
+}
+
+/// Parses the program arguments, not including the executable name.
+pub fn parse_args(args: &[String]) -> Result<Command, String> {
+    match args.first().map(|arg| arg.as_str()) {
+        None => Ok(Command::Run(RunOptions::default())),
+        Some("run") => Ok(Command::Run(parse_run_options(&args[1..], Extras::Run)?)),
+        Some("bench") => Ok(Command::Bench(parse_bench_options(&args[1..])?)),
+        Some("verify") => Ok(Command::Verify(parse_verify_options(&args[1..])?)),
+        Some("new") => parse_new_options(&args[1..]),
+        Some("list") if args.len() == 1 => Ok(Command::List),
+        Some("list") => Err(format!("unexpected argument '{}'", args[1])),
+        Some("help" | "-h" | "--help") => Ok(Command::Help),
+        Some(_) => Ok(Command::Run(parse_run_options(args, Extras::Run)?)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_args_defaults_to_running_everything() {
+        assert_eq!(
+            parse_args(&args("")),
+            Ok(Command::Run(RunOptions::default()))
+        );
+        assert_eq!(
+            parse_args(&args("run")),
+            Ok(Command::Run(RunOptions::default()))
+        );
+    }
+
+    #[test]
+    fn test_parse_args_single_day_and_part() {
+        assert_eq!(
+            parse_args(&args("run --day 13 --part 2")),
+            Ok(Command::Run(RunOptions {
+                selection: Selection {
+                    days: vec![13],
+                    part: Some(2),
+                },
+                ..RunOptions::default()
+            }))
+        );
+        assert_eq!(
+            parse_args(&args("--day=4 -p 1")),
+            Ok(Command::Run(RunOptions {
+                selection: Selection {
+                    days: vec![4],
+                    part: Some(1),
+                },
+                ..RunOptions::default()
+            }))
+        );
+    }
+
+    #[test]
+    fn test_parse_args_day_ranges() {
+        assert_eq!(
+            parse_args(&args("run --days 5-9 --day 1 --days 2,8")),
+            Ok(Command::Run(RunOptions {
+                selection: Selection {
+                    days: vec![1, 2, 5, 6, 7, 8, 9],
+                    part: None,
+                },
+                ..RunOptions::default()
+            }))
+        );
+    }
+
+    #[test]
+    fn test_parse_args_input_overrides() {
+        assert_eq!(
+            parse_args(&args("run --day 4 --input mine.txt")),
+            Ok(Command::Run(RunOptions {
+                selection: Selection {
+                    days: vec![4],
+                    part: None,
+                },
+                input: Some("mine.txt".to_string()),
+                ..RunOptions::default()
+            }))
+        );
+        assert_eq!(
+            parse_args(&args("--input-dir ./other")),
+            Ok(Command::Run(RunOptions {
+                input_dir: Some("./other".to_string()),
+                ..RunOptions::default()
+            }))
+        );
+        assert!(parse_args(&args("run --input -")).is_err());
+        assert!(parse_args(&args("run --days 1-2 --input -")).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_format() {
+        assert_eq!(
+            parse_args(&args("run --format json")),
+            Ok(Command::Run(RunOptions {
+                format: OutputFormat::Json,
+                ..RunOptions::default()
+            }))
+        );
+        assert!(parse_args(&args("run --format yaml")).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_jobs() {
+        assert_eq!(
+            parse_args(&args("run --jobs 4")),
+            Ok(Command::Run(RunOptions {
+                jobs: 4,
+                ..RunOptions::default()
+            }))
+        );
+        assert_eq!(
+            parse_args(&args("-j=0")),
+            Ok(Command::Run(RunOptions {
+                jobs: 0,
+                ..RunOptions::default()
+            }))
+        );
+        assert!(parse_args(&args("run --jobs many")).is_err());
+        assert!(parse_args(&args("bench --jobs 4")).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_bench() {
+        assert_eq!(
+            parse_args(&args("bench")),
+            Ok(Command::Bench(BenchOptions::default()))
+        );
+        assert_eq!(
+            parse_args(&args("bench --day 15 -n 50 --warmup=0")),
+            Ok(Command::Bench(BenchOptions {
+                run: RunOptions {
+                    selection: Selection {
+                        days: vec![15],
+                        part: None,
+                    },
+                    ..RunOptions::default()
+                },
+                iterations: 50,
+                warmup: 0,
+            }))
+        );
+        assert!(parse_args(&args("bench --iterations 0")).is_err());
+        assert!(parse_args(&args("bench --format json")).is_err());
+        assert!(parse_args(&args("run --iterations 5")).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_show_moves() {
+        assert_eq!(
+            parse_args(&args("--day 23 --show-moves")),
+            Ok(Command::Run(RunOptions {
+                selection: Selection {
+                    days: vec![23],
+                    part: None,
+                },
+                show_moves: true,
+                ..RunOptions::default()
+            }))
+        );
+        assert!(parse_args(&args("run --show-moves=yes")).is_err());
+        assert!(parse_args(&args("bench --day 23 --show-moves")).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_verify() {
+        assert_eq!(
+            parse_args(&args("verify")),
+            Ok(Command::Verify(VerifyOptions::default()))
+        );
+        assert_eq!(
+            parse_args(&args("verify --days 1-2 --answers friends.toml -j 0")),
+            Ok(Command::Verify(VerifyOptions {
+                run: RunOptions {
+                    selection: Selection {
+                        days: vec![1, 2],
+                        part: None,
+                    },
+                    jobs: 0,
+                    ..RunOptions::default()
+                },
+                answers: "friends.toml".to_string(),
+            }))
+        );
+        assert!(parse_args(&args("verify --day 4 --input mine.txt")).is_err());
+        assert!(parse_args(&args("verify --format json")).is_err());
+        assert!(parse_args(&args("run --answers answers.toml")).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_new() {
+        let new = ["new", "--day", "17", "--title", "Trick Shot"].map(String::from);
+        assert_eq!(
+            parse_args(&new),
+            Ok(Command::New {
+                day: 17,
+                title: "Trick Shot".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_args(&args("new -d=3 -t=Binary")),
+            Ok(Command::New {
+                day: 3,
+                title: "Binary".to_string(),
+            })
+        );
+        assert!(parse_args(&args("new --day 17")).is_err());
+        assert!(parse_args(&args("new --title Shot")).is_err());
+        assert!(parse_args(&args("new --day 26 --title Shot")).is_err());
+        assert!(parse_args(&args("new --day 17 --title Shot --part 1")).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_list_and_help() {
+        assert_eq!(parse_args(&args("list")), Ok(Command::List));
+        assert_eq!(parse_args(&args("--help")), Ok(Command::Help));
+        assert!(parse_args(&args("list --day 3")).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_rejects_bad_values() {
+        assert!(parse_args(&args("run --day 0")).is_err());
+        assert!(parse_args(&args("run --day 26")).is_err());
+        assert!(parse_args(&args("run --days 9-5")).is_err());
+        assert!(parse_args(&args("run --part 3")).is_err());
+        assert!(parse_args(&args("run --day")).is_err());
+        assert!(parse_args(&args("run --verbose")).is_err());
+    }
+}
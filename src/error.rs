@@ -0,0 +1,115 @@
+use std::{error::Error, fmt, io, path::PathBuf};
+
+#[derive(Debug)]
+pub enum AocError {
+    MissingInput {
+        path: PathBuf,
+    },
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    /// Malformed puzzle input. Lines and columns are 1-indexed.
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// Letters drawn by the puzzle that the OCR font doesn't know, by
+    /// 0-indexed glyph position. Keeps the drawing so it can still be read.
+    UnrecognizedGlyphs {
+        positions: Vec<usize>,
+        rendered: Vec<String>,
+    },
+    /// Input that parsed fine, but that the puzzle has no answer for.
+    NoSolution {
+        reason: String,
+    },
+}
+
+impl AocError {
+    pub fn parse(line: usize, column: usize, message: impl Into<String>) -> AocError {
+        AocError::Parse {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    pub fn no_solution(reason: impl Into<String>) -> AocError {
+        AocError::NoSolution {
+            reason: reason.into(),
+        }
+    }
+
+    pub fn from_io(path: impl Into<PathBuf>, source: io::Error) -> AocError {
+        match source.kind() {
+            io::ErrorKind::NotFound => AocError::MissingInput { path: path.into() },
+            _ => AocError::Io {
+                path: path.into(),
+                source,
+            },
+        }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AocError::MissingInput { path } => {
+                write!(f, "input file {} does not exist", path.display())
+            }
+            AocError::Io { path, source } => {
+                write!(f, "could not read {}: {}", path.display(), source)
+            }
+            AocError::Parse {
+                line,
+                column,
+                message,
+            } => write!(f, "line {}, column {}: {}", line, column, message),
+            AocError::UnrecognizedGlyphs {
+                positions,
+                rendered,
+            } => write!(
+                f,
+                "could not recognize letters at positions {:?}\n{}",
+                positions,
+                rendered.join("\n")
+            ),
+            AocError::NoSolution { reason } => write!(f, "no solution: {}", reason),
+        }
+    }
+}
+
+impl Error for AocError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AocError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_io_maps_not_found_to_missing_input() {
+        let error = AocError::from_io("nope.txt", io::Error::from(io::ErrorKind::NotFound));
+        assert!(matches!(error, AocError::MissingInput { .. }));
+        assert_eq!(error.to_string(), "input file nope.txt does not exist");
+
+        let error = AocError::from_io("dir", io::Error::from(io::ErrorKind::PermissionDenied));
+        assert!(matches!(error, AocError::Io { .. }));
+        assert!(error.source().is_some());
+    }
+
+    #[test]
+    fn test_parse_error_display() {
+        assert_eq!(
+            AocError::parse(3, 7, "expected a number").to_string(),
+            "line 3, column 7: expected a number"
+        );
+    }
+}
@@ -0,0 +1,320 @@
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use crate::error::AocError;
+
+/// Offsets to the four orthogonal neighbours, clockwise from up.
+const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+/// Offsets to all eight neighbours, row by row.
+const SURROUNDING: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// A rectangular grid, stored row by row. Positions are `(row, col)`, from
+/// `(0, 0)` at the top left.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A `width` by `height` grid with every cell set to `value`. Panics if
+    /// `width` is 0, since rows need at least one cell.
+    pub fn new(width: usize, height: usize, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        assert!(width > 0, "a grid must be at least one column wide");
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Parses a map with one character per cell and one row per line.
+    /// `cell` turns a character into a cell, or `None` if it isn't one;
+    /// `expected` describes the characters it accepts, for error messages.
+    /// Every row must be the same, non-zero, length.
+    pub fn parse(
+        input: &str,
+        expected: &str,
+        cell: impl Fn(char) -> Option<T>,
+    ) -> Result<Grid<T>, AocError> {
+        Grid::parse_lines(input.lines().enumerate(), expected, cell)
+    }
+
+    /// Like `parse`, for a map that starts partway through the input. Takes
+    /// lines with their 0-indexed line numbers, as given by `enumerate()`.
+    pub fn parse_lines<'a>(
+        lines: impl Iterator<Item = (usize, &'a str)>,
+        expected: &str,
+        cell: impl Fn(char) -> Option<T>,
+    ) -> Result<Grid<T>, AocError> {
+        let mut cells = Vec::new();
+        let mut width = 0;
+        let mut height = 0;
+        for (index, line) in lines {
+            let length = line.chars().count();
+            if length == 0 || (height > 0 && length != width) {
+                return Err(AocError::parse(
+                    index + 1,
+                    1,
+                    format!("expected {} cells, found {}", width.max(1), length),
+                ));
+            }
+            for (column, c) in line.chars().enumerate() {
+                match cell(c) {
+                    Some(value) => cells.push(value),
+                    None => {
+                        return Err(AocError::parse(
+                            index + 1,
+                            column + 1,
+                            format!("expected {}, found '{}'", expected, c),
+                        ))
+                    }
+                }
+            }
+            width = length;
+            height += 1;
+        }
+        match height {
+            0 => Err(AocError::parse(
+                1,
+                1,
+                format!("expected a grid of {}", expected),
+            )),
+            _ => Ok(Grid {
+                width,
+                height,
+                cells,
+            }),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether `(row, col)` is inside the grid.
+    pub fn contains(&self, (row, col): (usize, usize)) -> bool {
+        row < self.height && col < self.width
+    }
+
+    pub fn get(&self, position: (usize, usize)) -> Option<&T> {
+        match self.contains(position) {
+            true => Some(&self.cells[position.0 * self.width + position.1]),
+            false => None,
+        }
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// The cells of one row, left to right.
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    /// The cells of one column, top to bottom.
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            col < self.width,
+            "column {} is outside a grid {} wide",
+            col,
+            self.width
+        );
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+
+    /// The positions up, right, down and left of `position` that are inside
+    /// the grid.
+    pub fn neighbors(&self, position: (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        self.offsets(position, &ORTHOGONAL)
+    }
+
+    /// Like `neighbors`, but including the four diagonals.
+    pub fn surrounding(&self, position: (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        self.offsets(position, &SURROUNDING)
+    }
+
+    fn offsets(
+        &self,
+        (row, col): (usize, usize),
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = (self.width, self.height);
+        offsets.iter().filter_map(move |(row_offset, col_offset)| {
+            let row = row.checked_add_signed(*row_offset)?;
+            let col = col.checked_add_signed(*col_offset)?;
+            (row < height && col < width).then_some((row, col))
+        })
+    }
+
+    /// A grid of the same size, with `f` applied to every cell.
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Draws every row as a string, with `f` picking each cell's character.
+    pub fn render(&self, f: impl Fn(&T) -> char) -> Vec<String> {
+        self.cells
+            .chunks(self.width)
+            .map(|row| row.iter().map(&f).collect())
+            .collect()
+    }
+}
+
+impl Grid<u8> {
+    /// Parses a grid of single decimal digits, e.g. a heightmap.
+    pub fn parse_digits(input: &str) -> Result<Grid<u8>, AocError> {
+        Grid::parse(input, "a digit", |c| {
+            c.to_digit(10).map(|digit| digit as u8)
+        })
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        assert!(
+            col < self.width,
+            "column {} is outside a grid {} wide",
+            col,
+            self.width
+        );
+        &self.cells[row * self.width + col]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        assert!(
+            col < self.width,
+            "column {} is outside a grid {} wide",
+            col,
+            self.width
+        );
+        &mut self.cells[row * self.width + col]
+    }
+}
+
+/// Prints one row per line, with no separators between cells, so a grid
+/// of digits prints the way it was parsed.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, row) in self.cells.chunks(self.width).enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_digits() {
+        let grid = Grid::parse_digits("219\n398").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 2)], 8);
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.to_string(), "219\n398");
+        match Grid::parse_digits("219\n3a8") {
+            Err(AocError::Parse { line, column, .. }) => assert_eq!((line, column), (2, 2)),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+        assert!(Grid::parse_digits("219\n39").is_err());
+        assert!(Grid::parse_digits("").is_err());
+    }
+
+    #[test]
+    fn test_neighbors_stay_in_bounds() {
+        let grid = Grid::new(4, 2, 0);
+        assert_eq!(
+            grid.neighbors((0, 0)).collect::<Vec<_>>(),
+            vec![(0, 1), (1, 0)]
+        );
+        assert_eq!(
+            grid.neighbors((1, 2)).collect::<Vec<_>>(),
+            vec![(0, 2), (1, 3), (1, 1)]
+        );
+        assert_eq!(grid.surrounding((0, 3)).count(), 3);
+        assert_eq!(grid.surrounding((1, 1)).count(), 5);
+    }
+
+    #[test]
+    fn test_views_and_render() {
+        let grid = Grid::parse("#..\n.##", "'#' or '.'", |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!(grid.row(1), &[false, true, true]);
+        assert_eq!(
+            grid.column(0).copied().collect::<Vec<_>>(),
+            vec![true, false]
+        );
+        assert_eq!(
+            grid.render(|lit| if *lit { '#' } else { '.' }),
+            vec!["#..", ".##"]
+        );
+        assert_eq!(grid.map(|lit| *lit as u8).to_string(), "100\n011");
+        assert_eq!(
+            grid.iter()
+                .filter(|(_, lit)| **lit)
+                .map(|(position, _)| position)
+                .collect::<Vec<_>>(),
+            vec![(0, 0), (1, 1), (1, 2)]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 is outside a grid 3 wide")]
+    fn test_column_outside_the_grid_panics() {
+        let grid = Grid::new(3, 2, 0);
+        grid.column(3).count();
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one column wide")]
+    fn test_new_rejects_zero_width() {
+        Grid::new(0, 2, 0);
+    }
+}
@@ -0,0 +1,101 @@
+use std::{
+    env,
+    io::{self, Read},
+    path::PathBuf,
+};
+
+use crate::{error::AocError, problem::Solver, util::read_file};
+
+/// Environment variable naming a directory of `problem_XX.txt` inputs.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Where a day's puzzle input is read from. Anything that can't be found
+/// falls back to the input bundled with the binary.
+#[derive(Debug, PartialEq)]
+pub enum InputSource {
+    Bundled,
+    Stdin,
+    File(PathBuf),
+    Directory(PathBuf),
+}
+
+impl InputSource {
+    /// Picks the source for a run. An explicit `--input` wins over
+    /// `--input-dir`, which wins over `AOC_INPUT_DIR`.
+    pub fn from_options(input: Option<&str>, input_dir: Option<&str>) -> InputSource {
+        match (input, input_dir) {
+            (Some("-"), _) => InputSource::Stdin,
+            (Some(path), _) => InputSource::File(PathBuf::from(path)),
+            (None, Some(dir)) => InputSource::Directory(PathBuf::from(dir)),
+            (None, None) => match env::var_os(INPUT_DIR_VAR) {
+                Some(dir) if !dir.is_empty() => InputSource::Directory(PathBuf::from(dir)),
+                _ => InputSource::Bundled,
+            },
+        }
+    }
+
+    pub fn load(&self, problem: &dyn Solver) -> Result<String, AocError> {
+        match self {
+            InputSource::Bundled => problem.default_input(),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(|e| AocError::from_io("<stdin>", e))?;
+                Ok(trim_trailing_newlines(input))
+            }
+            InputSource::File(path) => read_file(path).map(trim_trailing_newlines),
+            InputSource::Directory(dir) => {
+                let path = dir.join(input_file_name(problem.day()));
+                match path.is_file() {
+                    true => read_file(&path).map(trim_trailing_newlines),
+                    false => problem.default_input(),
+                }
+            }
+        }
+    }
+}
+
+// Inputs saved straight from the website end with a newline, which some of
+// the parsers (e.g. the comma separated ones) would choke on.
+fn trim_trailing_newlines(mut input: String) -> String {
+    input.truncate(input.trim_end_matches(['\r', '\n']).len());
+    input
+}
+
+pub fn input_file_name(day: u8) -> String {
+    format!("problem_{:02}.txt", day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_options_prefers_explicit_input() {
+        assert_eq!(
+            InputSource::from_options(Some("-"), Some("inputs")),
+            InputSource::Stdin
+        );
+        assert_eq!(
+            InputSource::from_options(Some("mine.txt"), Some("inputs")),
+            InputSource::File(PathBuf::from("mine.txt"))
+        );
+        assert_eq!(
+            InputSource::from_options(None, Some("inputs")),
+            InputSource::Directory(PathBuf::from("inputs"))
+        );
+    }
+
+    #[test]
+    fn test_trim_trailing_newlines() {
+        assert_eq!(trim_trailing_newlines("3,4,3\r\n\n".to_string()), "3,4,3");
+        assert_eq!(trim_trailing_newlines("a\n\nb".to_string()), "a\n\nb");
+    }
+
+    #[test]
+    fn test_input_file_name() {
+        assert_eq!(input_file_name(4), "problem_04.txt");
+        assert_eq!(input_file_name(16), "problem_16.txt");
+    }
+}
@@ -0,0 +1,115 @@
+//! Solutions to Advent of Code 2021, usable as a library.
+//!
+//! Every day implements [`problem::Problem`]: parse an input once, then
+//! solve either part from the parsed value. Each `problem_XX` module also
+//! exposes that day's domain types and its `solve_actual` and
+//! `solve_actual_part2` functions. [`problems`] lists every day as a
+//! [`problem::Solver`], which is what the runner and the binary use.
+//!
+//! To add a day, add its module and title to the `days!` list at the
+//! bottom.
+
+#[macro_use]
+mod macros;
+pub mod answer;
+pub mod answers;
+pub mod bench;
+pub mod error;
+pub mod grid;
+pub mod input;
+pub mod ocr;
+pub mod problem;
+pub mod report;
+pub mod runner;
+pub mod search;
+pub mod util;
+
+// The binary's commands. They're here so the binary and the integration
+// tests can share them, but they aren't part of the library's API.
+#[doc(hidden)]
+pub mod cli;
+#[doc(hidden)]
+pub mod scaffold;
+#[doc(hidden)]
+pub mod verify;
+
+/// Panics, at compile time when called from a constant, unless `days` is
+/// 1, 2, 3... with every day listed once.
+const fn check_days(days: &[u8]) {
+    let mut index = 0;
+    while index < days.len() {
+        if index > 0 && days[index] == days[index - 1] {
+            panic!("a day is registered twice");
+        }
+        if days[index] as usize != index + 1 {
+            panic!("days must be registered in order from 1, with none missing");
+        }
+        index += 1;
+    }
+}
+
+days! {
+    1 => problem_01::Problem01 "Sonar Sweep",
+    2 => problem_02::Problem02 "Dive!",
+    3 => problem_03::Problem03 "Binary Diagnostic",
+    4 => problem_04::Problem04 "Giant Squid",
+    5 => problem_05::Problem05 "Hydrothermal Venture",
+    6 => problem_06::Problem06 "Lanternfish",
+    7 => problem_07::Problem07 "The Treachery of Whales",
+    8 => problem_08::Problem08 "Seven Segment Search",
+    9 => problem_09::Problem09 "Smoke Basin",
+    10 => problem_10::Problem10 "Syntax Scoring",
+    11 => problem_11::Problem11 "Dumbo Octopus",
+    12 => problem_12::Problem12 "Passage Pathing",
+    13 => problem_13::Problem13 "Transparent Origami",
+    14 => problem_14::Problem14 "Extended Polymerization",
+    15 => problem_15::Problem15 "Chiton",
+    16 => problem_16::Problem16 "Packet Decoder",
+    17 => problem_17::Problem17 "Trick Shot",
+    18 => problem_18::Problem18 "Snailfish",
+    19 => problem_19::Problem19 "Beacon Scanner",
+    20 => problem_20::Problem20 "Trench Map",
+    21 => problem_21::Problem21 "Dirac Dice",
+    22 => problem_22::Problem22 "Reactor Reboot",
+    23 => problem_23::Problem23 "Amphipod",
+    24 => problem_24::Problem24 "Arithmetic Logic Unit",
+    25 => problem_25::Problem25 "Sea Cucumber",
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registered_days_match_solvers() {
+        let problems = problems();
+        assert_eq!(problems.len(), DAYS.len());
+        for (day, problem) in DAYS.iter().zip(&problems) {
+            assert_eq!(problem.day(), *day);
+            assert!(
+                problem.name().starts_with(&format!("Day {}: ", day)),
+                "day {} is named '{}'",
+                day,
+                problem.name()
+            );
+        }
+    }
+
+    #[test]
+    fn test_check_days() {
+        check_days(&[1, 2, 3]);
+        check_days(&[]);
+    }
+
+    #[test]
+    #[should_panic(expected = "registered twice")]
+    fn test_check_days_rejects_duplicates() {
+        check_days(&[1, 2, 2, 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "none missing")]
+    fn test_check_days_rejects_gaps() {
+        check_days(&[1, 2, 4]);
+    }
+}
@@ -1,16 +1,45 @@
 #[cfg(debug_assertions)]
 macro_rules! get_input {
     ($path:expr) => {
-        match crate::util::read_file($path) {
-            Some(data) => data,
-            None => "".to_string(),
-        }
+        crate::util::read_file($path)
     };
 }
 
 #[cfg(not(debug_assertions))]
 macro_rules! get_input {
     ($path:expr) => {
-        include_str!(concat!(".", $path)).to_string()
+        Ok::<String, crate::error::AocError>(include_str!(concat!(".", $path)).to_string())
+    };
+}
+
+/// Declares every day's module and registers its solver, from entries like
+/// `1 => problem_01::Problem01 "Sonar Sweep"`. Gives each solver a `DAY` and
+/// a `NAME` such as `"Day 1: Sonar Sweep"`, which its `day()` and `name()`
+/// return, so the number and title are only written here. Generates `DAYS`,
+/// the registered day numbers, and `problems()`, every day's solver in the
+/// same order. Days must be listed once each, in order from 1; a duplicate
+/// or a gap fails to compile.
+macro_rules! days {
+    ($($day:literal => $module:ident::$problem:ident $title:literal),* $(,)?) => {
+        $(
+            pub mod $module;
+
+            impl $module::$problem {
+                /// The day this solver is registered as.
+                pub const DAY: u8 = $day;
+                /// The day's number and puzzle title.
+                pub const NAME: &'static str = concat!("Day ", $day, ": ", $title);
+            }
+        )*
+
+        /// The number of every registered day, in order.
+        pub const DAYS: &[u8] = &[$($day),*];
+
+        const _: () = check_days(DAYS);
+
+        /// Every day's solver, in order.
+        pub fn problems() -> Vec<Box<dyn $crate::problem::Solver>> {
+            vec![$(Box::new($module::$problem::new())),*]
+        }
     };
 }
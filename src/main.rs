@@ -1,70 +1,132 @@
-use std::time::Instant;
+use std::{env, num::NonZeroUsize, path::Path, process, thread};
 
-use crate::problem::Problem;
+use advent_of_code_2021::{
+    answers, bench,
+    cli::{self, Command, Selection},
+    input::InputSource,
+    problem::{Problem, Solver},
+    problem_23::Problem23,
+    problems, report, runner, scaffold, verify,
+};
 
-#[macro_use]
-mod macros;
-mod problem;
-mod problem_01;
-mod problem_02;
-mod problem_03;
-mod problem_04;
-mod problem_05;
-mod problem_06;
-mod problem_07;
-mod problem_08;
-mod problem_09;
-mod problem_10;
-mod problem_11;
-mod problem_12;
-mod problem_13;
-mod problem_14;
-mod problem_15;
-mod problem_16;
-mod util;
+/// Prints every state along the cheapest way to organize Day 23's burrow,
+/// for each selected part.
+fn show_moves(source: &InputSource, selection: &Selection) {
+    let problem = Problem23::new();
+    let burrow = match source
+        .load(&problem)
+        .and_then(|input| problem.parse(&input))
+    {
+        Ok(burrow) => burrow,
+        Err(error) => return eprintln!("Day 23: {}", error),
+    };
+    for part in (1..=2).filter(|part| selection.includes_part(*part)) {
+        let burrow = match part {
+            1 => burrow.clone(),
+            _ => burrow.unfold(),
+        };
+        eprintln!("Day 23, part {}:\n", part);
+        match burrow.optimal_moves() {
+            Ok(path) => {
+                for (state, spent) in path.nodes {
+                    eprintln!("{}\n(energy used: {})\n", burrow.render(state), spent);
+                }
+            }
+            Err(error) => eprintln!("{}\n", error),
+        }
+    }
+}
 
-fn main() {
-    println!("~ Advent of Code 2021 ~");
+fn list(problems: &[Box<dyn Solver>]) {
+    problems
+        .iter()
+        .for_each(|problem| println!("{}", problem.name()));
+}
+
+// `--jobs 0` means one job per CPU.
+fn job_count(jobs: usize) -> usize {
+    match jobs {
+        0 => thread::available_parallelism().map_or(1, NonZeroUsize::get),
+        jobs => jobs,
+    }
+}
 
-    let problems: Vec<Box<dyn Problem>> = vec![
-        Box::new(problem_01::Problem01::new()),
-        Box::new(problem_02::Problem02::new()),
-        Box::new(problem_03::Problem03::new()),
-        Box::new(problem_04::Problem04::new()),
-        Box::new(problem_05::Problem05::new()),
-        Box::new(problem_06::Problem06::new()),
-        Box::new(problem_07::Problem07::new()),
-        Box::new(problem_08::Problem08::new()),
-        Box::new(problem_09::Problem09::new()),
-        Box::new(problem_10::Problem10::new()),
-        Box::new(problem_11::Problem11::new()),
-        Box::new(problem_12::Problem12::new()),
-        Box::new(problem_13::Problem13::new()),
-        Box::new(problem_14::Problem14::new()),
-        Box::new(problem_15::Problem15::new()),
-        Box::new(problem_16::Problem16::new()),
-    ];
-    let mut duration = Instant::now().elapsed();
-    problems.iter().for_each(|problem| {
-        println!("{}", problem.name());
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let command = match cli::parse_args(&args) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("error: {}\n\n{}", message, cli::USAGE);
+            process::exit(2);
+        }
+    };
 
-        print!(" - Part 1: ");
-        let part1_start = Instant::now();
-        let part1_result = problem.solve();
-        let part1_duration = part1_start.elapsed();
-        duration += part1_duration;
-        println!("{} (took {:.2?})", part1_result, part1_duration);
+    let problems = problems();
 
-        print!(" - Part 2: ");
-        let part2_start = Instant::now();
-        let part2_result = problem.solve_part2();
-        let part2_duration = part2_start.elapsed();
-        duration += part2_duration;
-        println!("{} (took {:.2?})", part2_result.0, part2_duration);
-        match part2_result.1 {
-            Some(additional) => println!("{}", additional),
-            None => (),
+    match command {
+        Command::Run(options) => {
+            let source =
+                InputSource::from_options(options.input.as_deref(), options.input_dir.as_deref());
+            let report = runner::run(
+                &problems,
+                &options.selection,
+                &source,
+                job_count(options.jobs),
+            );
+            println!("{}", report::render(options.format, &report));
+            if options.show_moves && options.selection.includes_day(23) {
+                show_moves(&source, &options.selection);
+            }
+        }
+        Command::Bench(options) => {
+            let run = &options.run;
+            let source = InputSource::from_options(run.input.as_deref(), run.input_dir.as_deref());
+            let results = bench::bench(
+                &problems,
+                &run.selection,
+                &source,
+                options.iterations,
+                options.warmup,
+            );
+            println!(
+                "{}",
+                bench::render(&results, options.iterations, options.warmup)
+            );
+        }
+        Command::Verify(options) => {
+            let known = match answers::load(&options.answers) {
+                Ok(known) => known,
+                Err(error) => {
+                    eprintln!("error: {}", error);
+                    process::exit(2);
+                }
+            };
+            let checks = verify::verify(
+                &problems,
+                &options.run.selection,
+                &known,
+                job_count(options.run.jobs),
+            );
+            println!("{}", verify::render(&checks));
+            if checks
+                .iter()
+                .any(|check| matches!(check.verdict, verify::Verdict::Fail { .. }))
+            {
+                process::exit(1);
+            }
         }
-    });
-    println!("Took a total of {:.2?}", duration);
+        Command::New { day, title } => match scaffold::scaffold(Path::new("."), day, &title) {
+            Ok(written) => {
+                for path in written {
+                    println!("wrote {}", path.display());
+                }
+            }
+            Err(message) => {
+                eprintln!("error: {}", message);
+                process::exit(1);
+            }
+        },
+        Command::List => list(&problems),
+        Command::Help => println!("{}", cli::USAGE),
+    }
 }
@@ -0,0 +1,132 @@
+use crate::error::AocError;
+
+pub const GLYPH_WIDTH: usize = 4;
+pub const GLYPH_HEIGHT: usize = 6;
+// Each glyph is followed by a blank column.
+const GLYPH_SPACING: usize = GLYPH_WIDTH + 1;
+
+/// The letters of the 4x6 font used by the puzzles, row by row.
+const FONT: [(char, [&str; GLYPH_HEIGHT]); 17] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+fn glyph_at(rows: &[String], position: usize) -> Vec<String> {
+    let start = position * GLYPH_SPACING;
+    rows.iter()
+        .map(|row| {
+            let glyph: String = row.chars().skip(start).take(GLYPH_WIDTH).collect();
+            format!("{:.<width$}", glyph, width = GLYPH_WIDTH)
+        })
+        .collect()
+}
+
+/// Reads the letters drawn in `rows` with `#` for lit and `.` for unlit
+/// cells. Fails with the positions (0-indexed, left to right) of every
+/// glyph that isn't in the font.
+pub fn recognize(rows: &[String]) -> Result<String, AocError> {
+    let width = rows
+        .iter()
+        .map(|row| row.chars().count())
+        .max()
+        .unwrap_or(0);
+    let glyphs = width.div_ceil(GLYPH_SPACING);
+    let unrecognized = |positions: Vec<usize>| AocError::UnrecognizedGlyphs {
+        positions,
+        rendered: rows.to_vec(),
+    };
+    if glyphs == 0 {
+        return Err(unrecognized(Vec::new()));
+    }
+    if rows.len() != GLYPH_HEIGHT {
+        return Err(unrecognized((0..glyphs).collect()));
+    }
+
+    let mut letters = String::with_capacity(glyphs);
+    let mut unknown = Vec::new();
+    for position in 0..glyphs {
+        let glyph = glyph_at(rows, position);
+        match FONT
+            .iter()
+            .find(|(_, pattern)| glyph.iter().eq(pattern.iter()))
+        {
+            Some((letter, _)) => letters.push(*letter),
+            None => unknown.push(position),
+        }
+    }
+    match unknown.is_empty() {
+        true => Ok(letters),
+        false => Err(unrecognized(unknown)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(letters: &str) -> Vec<String> {
+        (0..GLYPH_HEIGHT)
+            .map(|row| {
+                letters
+                    .chars()
+                    .map(|letter| {
+                        let (_, pattern) = FONT.iter().find(|(c, _)| *c == letter).unwrap();
+                        format!("{}.", pattern[row])
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_recognize_every_letter() {
+        let alphabet: String = FONT.iter().map(|(letter, _)| letter).collect();
+        assert_eq!(recognize(&render(&alphabet)).unwrap(), alphabet);
+    }
+
+    #[test]
+    fn test_recognize_without_trailing_spacer() {
+        let mut rows = render("ZUJ");
+        rows.iter_mut().for_each(|row| {
+            row.pop();
+        });
+        assert_eq!(recognize(&rows).unwrap(), "ZUJ");
+    }
+
+    #[test]
+    fn test_recognize_reports_unknown_positions() {
+        let mut rows = render("HELLO");
+        rows[0].replace_range(5..9, "#..#");
+        rows[5].replace_range(20..24, "####");
+        match recognize(&rows) {
+            Err(AocError::UnrecognizedGlyphs { positions, .. }) => {
+                assert_eq!(positions, vec![1, 4])
+            }
+            other => panic!("expected unrecognized glyphs, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_recognize_wrong_height() {
+        let rows: Vec<String> = vec!["#####".to_string(); 7];
+        match recognize(&rows) {
+            Err(AocError::UnrecognizedGlyphs { positions, .. }) => assert_eq!(positions, vec![0]),
+            other => panic!("expected unrecognized glyphs, got {:?}", other),
+        }
+    }
+}
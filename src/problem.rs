@@ -1,5 +1,89 @@
+use std::any::Any;
+
+use crate::{answer::Answer, error::AocError};
+
+/// A day's puzzle. The input is parsed once into `Parsed`, and both parts
+/// are solved from that same value.
 pub trait Problem {
+    type Parsed;
+
     fn name(&self) -> &str;
-    fn solve(&self) -> i64;
-    fn solve_part2(&self) -> (i64, Option<String>);
+    fn day(&self) -> u8;
+    fn default_input(&self) -> Result<String, AocError>;
+    fn parse(&self, input: &str) -> Result<Self::Parsed, AocError>;
+    fn part1(&self, parsed: &Self::Parsed) -> Result<Answer, AocError>;
+    fn part2(&self, parsed: &Self::Parsed) -> Result<Answer, AocError>;
+}
+
+/// The parsed input of some `Problem`, with its type erased.
+pub type ParsedInput = Box<dyn Any + Send + Sync>;
+
+/// An object safe view of a `Problem`, so days with different parsed types
+/// can live in the same list. Implemented for every `Problem`. Solvers are
+/// shared between the runner's threads, and so are parsed inputs.
+pub trait Solver: Sync {
+    fn name(&self) -> &str;
+    fn day(&self) -> u8;
+    fn default_input(&self) -> Result<String, AocError>;
+    fn parse_input(&self, input: &str) -> Result<ParsedInput, AocError>;
+
+    /// Solves `part` from the result of this solver's `parse_input`.
+    ///
+    /// Panics if `parsed` came from a different solver.
+    fn solve_part(&self, parsed: &ParsedInput, part: u8) -> Result<Answer, AocError>;
+}
+
+impl<P> Solver for P
+where
+    P: Problem + Sync,
+    P::Parsed: Send + Sync + 'static,
+{
+    fn name(&self) -> &str {
+        Problem::name(self)
+    }
+
+    fn day(&self) -> u8 {
+        Problem::day(self)
+    }
+
+    fn default_input(&self) -> Result<String, AocError> {
+        Problem::default_input(self)
+    }
+
+    fn parse_input(&self, input: &str) -> Result<ParsedInput, AocError> {
+        Ok(Box::new(self.parse(input)?))
+    }
+
+    fn solve_part(&self, parsed: &ParsedInput, part: u8) -> Result<Answer, AocError> {
+        let parsed = parsed
+            .downcast_ref::<P::Parsed>()
+            .expect("parsed input belongs to a different day");
+        match part {
+            1 => self.part1(parsed),
+            _ => self.part2(parsed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::problem_01::Problem01;
+
+    #[test]
+    fn test_solver_parses_once_for_both_parts() {
+        let solver: Box<dyn Solver> = Box::new(Problem01::new());
+        let parsed = solver
+            .parse_input("199\n200\n208\n210\n200\n207\n240\n269\n260\n263")
+            .unwrap();
+        assert_eq!(solver.solve_part(&parsed, 1).unwrap(), Answer::Integer(7));
+        assert_eq!(solver.solve_part(&parsed, 2).unwrap(), Answer::Integer(5));
+    }
+
+    #[test]
+    #[should_panic(expected = "different day")]
+    fn test_solver_rejects_input_parsed_by_another_day() {
+        let parsed: ParsedInput = Box::new("not a list of depths");
+        let _ = Problem01::new().solve_part(&parsed, 1);
+    }
 }
@@ -1,5 +1,6 @@
-use crate::problem::Problem;
+use crate::{answer::Answer, error::AocError, problem::Problem, util::parse_token};
 
+#[derive(Default)]
 pub struct Problem01 {}
 
 impl Problem01 {
@@ -7,30 +8,25 @@ impl Problem01 {
         Problem01 {}
     }
 
-    fn parse(&self, input: String) -> Vec<i64> {
-        input
-            .lines()
-            .map(|line| line.parse::<i64>().unwrap())
-            .collect()
-    }
-
-    fn solve_actual(&self, measurements: &Vec<i64>) -> i64 {
+    pub fn solve_actual(&self, measurements: &[i64]) -> i64 {
         let mut increases = 0;
-        let mut window = measurements.windows(2);
-        while let Some([prev, next]) = window.next() {
-            if next > prev {
-                increases += 1;
+        for window in measurements.windows(2) {
+            if let [prev, next] = window {
+                if next > prev {
+                    increases += 1;
+                }
             }
         }
         increases
     }
 
-    fn solve_actual_part2(&self, measurements: &Vec<i64>) -> i64 {
+    pub fn solve_actual_part2(&self, measurements: &[i64]) -> i64 {
         let mut increases = 0;
-        let mut window = measurements.windows(4);
-        while let Some([first, second, third, fourth]) = window.next() {
-            if (second + third + fourth) > (first + second + third) {
-                increases += 1;
+        for window in measurements.windows(4) {
+            if let [first, second, third, fourth] = window {
+                if (second + third + fourth) > (first + second + third) {
+                    increases += 1;
+                }
             }
         }
         increases
@@ -38,20 +34,34 @@ impl Problem01 {
 }
 
 impl Problem for Problem01 {
+    type Parsed = Vec<i64>;
+
     fn name(&self) -> &str {
-        "Day 1: Sonar Sweep"
+        Self::NAME
+    }
+
+    fn day(&self) -> u8 {
+        Self::DAY
+    }
+
+    fn default_input(&self) -> Result<String, AocError> {
+        get_input!("./inputs/problem_01.txt")
     }
 
-    fn solve(&self) -> i64 {
-        let input = get_input!("./inputs/problem_01.txt");
-        let measurements = self.parse(input);
-        self.solve_actual(&measurements)
+    fn parse(&self, input: &str) -> Result<Self::Parsed, AocError> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(index, line)| parse_token(index, line, line))
+            .collect()
+    }
+
+    fn part1(&self, measurements: &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual(measurements).into())
     }
 
-    fn solve_part2(&self) -> (i64, Option<String>) {
-        let input = get_input!("./inputs/problem_01.txt");
-        let measurements = self.parse(input);
-        (self.solve_actual_part2(&measurements), None)
+    fn part2(&self, measurements: &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual_part2(measurements).into())
     }
 }
 
@@ -80,6 +90,15 @@ mod tests {
         assert_eq!(problem.solve_actual(&measurements), 0);
     }
 
+    #[test]
+    fn test_parse_reports_bad_line() {
+        let problem = Problem01::new();
+        match problem.parse("199\n200\n2O8") {
+            Err(AocError::Parse { line, column, .. }) => assert_eq!((line, column), (3, 1)),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_solve_actual_part2_from_example() {
         let problem = Problem01::new();
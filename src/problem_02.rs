@@ -1,10 +1,11 @@
-use crate::problem::Problem;
+use crate::{answer::Answer, error::AocError, problem::Problem, util::parse_token};
 
-struct Command {
+pub struct Command {
     instruction: String,
     amount: i64,
 }
 
+#[derive(Default)]
 pub struct Problem02 {}
 
 impl Problem02 {
@@ -12,19 +13,7 @@ impl Problem02 {
         Problem02 {}
     }
 
-    fn parse(&self, input: String) -> Vec<Command> {
-        let mut commands: Vec<Command> = Vec::new();
-        input.lines().for_each(|line| {
-            let split: Vec<&str> = line.split_ascii_whitespace().collect();
-            commands.push(Command {
-                instruction: split[0].to_string(),
-                amount: split[1].parse::<i64>().unwrap(),
-            });
-        });
-        commands
-    }
-
-    fn solve_actual(&self, commands: &Vec<Command>) -> i64 {
+    pub fn solve_actual(&self, commands: &[Command]) -> i64 {
         let mut depth: i64 = 0;
         let mut distance: i64 = 0;
         commands
@@ -38,7 +27,7 @@ impl Problem02 {
         depth * distance
     }
 
-    fn solve_actual_part2(&self, commands: &Vec<Command>) -> i64 {
+    pub fn solve_actual_part2(&self, commands: &[Command]) -> i64 {
         let mut depth: i64 = 0;
         let mut distance: i64 = 0;
         let mut aim: i64 = 0;
@@ -58,20 +47,54 @@ impl Problem02 {
 }
 
 impl Problem for Problem02 {
+    type Parsed = Vec<Command>;
+
     fn name(&self) -> &str {
-        "Day 2: Dive!"
+        Self::NAME
     }
 
-    fn solve(&self) -> i64 {
-        let input = get_input!("./inputs/problem_02.txt");
-        let commands: Vec<Command> = self.parse(input);
-        self.solve_actual(&commands)
+    fn day(&self) -> u8 {
+        Self::DAY
     }
 
-    fn solve_part2(&self) -> (i64, Option<String>) {
-        let input = get_input!("./inputs/problem_02.txt");
-        let commands: Vec<Command> = self.parse(input);
-        (self.solve_actual_part2(&commands), None)
+    fn default_input(&self) -> Result<String, AocError> {
+        get_input!("./inputs/problem_02.txt")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, AocError> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(index, line)| {
+                let split: Vec<&str> = line.split_ascii_whitespace().collect();
+                if split.len() != 2 {
+                    return Err(AocError::parse(
+                        index + 1,
+                        1,
+                        "expected an instruction and an amount",
+                    ));
+                }
+                if !["forward", "down", "up"].contains(&split[0]) {
+                    return Err(AocError::parse(
+                        index + 1,
+                        1,
+                        format!("unknown instruction '{}'", split[0]),
+                    ));
+                }
+                Ok(Command {
+                    instruction: split[0].to_string(),
+                    amount: parse_token(index, line, split[1])?,
+                })
+            })
+            .collect()
+    }
+
+    fn part1(&self, commands: &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual(commands).into())
+    }
+
+    fn part2(&self, commands: &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual_part2(commands).into())
     }
 }
 
@@ -82,8 +105,8 @@ mod tests {
     #[test]
     fn test_solve_actual_from_example() {
         let problem = Problem02::new();
-        let input = get_input!("./inputs/problem_02_example.txt");
-        let commands = problem.parse(input);
+        let input = get_input!("./inputs/problem_02_example.txt").unwrap();
+        let commands = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual(&commands), 150);
     }
 
@@ -135,11 +158,21 @@ mod tests {
         assert_eq!(problem.solve_actual(&commands), 0);
     }
 
+    #[test]
+    fn test_parse_rejects_unknown_instruction() {
+        let problem = Problem02::new();
+        assert!(problem.parse("forward 5\nbackward 3").is_err());
+        match problem.parse("forward 5\ndown x") {
+            Err(AocError::Parse { line, column, .. }) => assert_eq!((line, column), (2, 6)),
+            other => panic!("expected a parse error, got {:?}", other.map(|c| c.len())),
+        }
+    }
+
     #[test]
     fn test_solve_actual_part2_from_example() {
         let problem = Problem02::new();
-        let input = get_input!("./inputs/problem_02_example.txt");
-        let commands = problem.parse(input);
+        let input = get_input!("./inputs/problem_02_example.txt").unwrap();
+        let commands = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual_part2(&commands), 900);
     }
 
@@ -1,7 +1,8 @@
 use std::str;
 
-use crate::problem::Problem;
+use crate::{answer::Answer, error::AocError, problem::Problem};
 
+#[derive(Default)]
 pub struct Problem03 {}
 
 impl Problem03 {
@@ -9,13 +10,6 @@ impl Problem03 {
         Problem03 {}
     }
 
-    fn parse(&self, input: String) -> Vec<Vec<u8>> {
-        input
-            .lines()
-            .map(|line| line.as_bytes().to_owned())
-            .collect()
-    }
-
     // Epsilon rate is just the ones complement of the gamma rate. We can
     // invert by taking the max integer value for the number of bits in the
     // input and subtracting the gamma rate.
@@ -27,15 +21,14 @@ impl Problem03 {
         2i64.pow(size as u32) - 1 - num
     }
 
-    fn solve_actual(&self, diagnostics: &Vec<Vec<u8>>) -> i64 {
-        if diagnostics.len() == 0 {
+    pub fn solve_actual(&self, diagnostics: &[Vec<u8>]) -> i64 {
+        if diagnostics.is_empty() {
             return 0;
         }
 
         let bits = diagnostics[0].len();
         let mut gamma_rate: i64 = 0;
-        let mut index = 0;
-        while index < bits {
+        for index in 0..bits {
             let mut ones_count = 0;
             diagnostics.iter().for_each(|num| {
                 if num[index] as char == '1' {
@@ -47,15 +40,14 @@ impl Problem03 {
             if ones_count > (diagnostics.len() / 2) {
                 gamma_rate = (gamma_rate << 1) + 1;
             } else {
-                gamma_rate = gamma_rate << 1;
+                gamma_rate <<= 1;
             }
-            index += 1;
         }
 
         gamma_rate * self.epsilon_rate(bits, gamma_rate)
     }
 
-    fn calculate_rating(&self, values: &Vec<Vec<u8>>, invert: bool, index: usize) -> i64 {
+    fn calculate_rating(&self, values: &[Vec<u8>], invert: bool, index: usize) -> i64 {
         if values.len() == 1 {
             return i64::from_str_radix(str::from_utf8(&values[0]).unwrap(), 2).unwrap();
         }
@@ -70,7 +62,7 @@ impl Problem03 {
         let halfway = values.len() / 2;
         let mut invert_bit = '1';
         let mut normal_bit = '0';
-        if ones_count > halfway || ((ones_count == halfway) && (values.len() % 2 == 0)) {
+        if ones_count > halfway || ((ones_count == halfway) && values.len() % 2 == 0) {
             invert_bit = '0';
             normal_bit = '1';
         }
@@ -85,33 +77,65 @@ impl Problem03 {
         self.calculate_rating(&values_to_keep, invert, index + 1)
     }
 
-    fn solve_actual_part2(&self, diagnostics: &Vec<Vec<u8>>) -> i64 {
-        if diagnostics.len() == 0 {
+    pub fn solve_actual_part2(&self, diagnostics: &[Vec<u8>]) -> i64 {
+        if diagnostics.is_empty() {
             return 0;
         }
 
-        let oxygen_rating = self.calculate_rating(&diagnostics, false, 0);
-        let scrubber_rating = self.calculate_rating(&diagnostics, true, 0);
+        let oxygen_rating = self.calculate_rating(diagnostics, false, 0);
+        let scrubber_rating = self.calculate_rating(diagnostics, true, 0);
 
         oxygen_rating * scrubber_rating
     }
 }
 
 impl Problem for Problem03 {
+    type Parsed = Vec<Vec<u8>>;
+
     fn name(&self) -> &str {
-        "Day 3: Binary Diagnostic"
+        Self::NAME
     }
 
-    fn solve(&self) -> i64 {
-        let input = get_input!("./inputs/problem_03.txt");
-        let diagnostics: Vec<Vec<u8>> = self.parse(input);
-        self.solve_actual(&diagnostics)
+    fn day(&self) -> u8 {
+        Self::DAY
     }
 
-    fn solve_part2(&self) -> (i64, Option<String>) {
-        let input = get_input!("./inputs/problem_03.txt");
-        let diagnostics: Vec<Vec<u8>> = self.parse(input);
-        (self.solve_actual_part2(&diagnostics), None)
+    fn default_input(&self) -> Result<String, AocError> {
+        get_input!("./inputs/problem_03.txt")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, AocError> {
+        let mut diagnostics: Vec<Vec<u8>> = Vec::new();
+        for (index, line) in input.lines().enumerate() {
+            if let Some(column) = line.find(|c| c != '0' && c != '1') {
+                return Err(AocError::parse(
+                    index + 1,
+                    column + 1,
+                    "expected a binary digit",
+                ));
+            }
+            if !diagnostics.is_empty() && line.len() != diagnostics[0].len() {
+                return Err(AocError::parse(
+                    index + 1,
+                    1,
+                    format!(
+                        "expected {} bits, found {}",
+                        diagnostics[0].len(),
+                        line.len()
+                    ),
+                ));
+            }
+            diagnostics.push(line.as_bytes().to_owned());
+        }
+        Ok(diagnostics)
+    }
+
+    fn part1(&self, diagnostics: &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual(diagnostics).into())
+    }
+
+    fn part2(&self, diagnostics: &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual_part2(diagnostics).into())
     }
 }
 
@@ -122,32 +146,42 @@ mod tests {
     #[test]
     fn test_solve_actual_from_example() {
         let problem = Problem03::new();
-        let input = get_input!("./inputs/problem_03_example.txt");
-        let diagnostics: Vec<Vec<u8>> = problem.parse(input);
+        let input = get_input!("./inputs/problem_03_example.txt").unwrap();
+        let diagnostics: Vec<Vec<u8>> = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual(&diagnostics), 198);
     }
 
     #[test]
     fn test_solve_actual_from_input() {
         let problem = Problem03::new();
-        let input = get_input!("./inputs/problem_03.txt");
-        let diagnostics: Vec<Vec<u8>> = problem.parse(input);
+        let input = get_input!("./inputs/problem_03.txt").unwrap();
+        let diagnostics: Vec<Vec<u8>> = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual(&diagnostics), 1997414);
     }
 
+    #[test]
+    fn test_parse_rejects_malformed_diagnostics() {
+        let problem = Problem03::new();
+        match problem.parse("00100\n11120") {
+            Err(AocError::Parse { line, column, .. }) => assert_eq!((line, column), (2, 4)),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+        assert!(problem.parse("00100\n1111").is_err());
+    }
+
     #[test]
     fn test_solve_actual_part2_from_example() {
         let problem = Problem03::new();
-        let input = get_input!("./inputs/problem_03_example.txt");
-        let diagnostics: Vec<Vec<u8>> = problem.parse(input);
+        let input = get_input!("./inputs/problem_03_example.txt").unwrap();
+        let diagnostics: Vec<Vec<u8>> = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual_part2(&diagnostics), 230);
     }
 
     #[test]
     fn test_solve_actual_part2_from_input() {
         let problem = Problem03::new();
-        let input = get_input!("./inputs/problem_03.txt");
-        let diagnostics: Vec<Vec<u8>> = problem.parse(input);
+        let input = get_input!("./inputs/problem_03.txt").unwrap();
+        let diagnostics: Vec<Vec<u8>> = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual_part2(&diagnostics), 1032597);
     }
 }
@@ -1,6 +1,6 @@
 use std::collections::{HashMap, HashSet};
 
-use crate::problem::Problem;
+use crate::{answer::Answer, error::AocError, problem::Problem, util::parse_token};
 
 pub struct BingoBoard {
     actual_board: HashMap<i64, (usize, usize)>,
@@ -9,14 +9,14 @@ pub struct BingoBoard {
 }
 
 impl BingoBoard {
-    pub fn new(board: &Vec<Vec<i64>>) -> BingoBoard {
+    pub fn new(board: &[Vec<i64>]) -> BingoBoard {
         let mut actual_board: HashMap<i64, (usize, usize)> = HashMap::new();
         let mut marked_board: HashMap<(usize, usize), bool> = HashMap::new();
 
         board.iter().enumerate().for_each(|(row_index, row)| {
             row.iter().enumerate().for_each(|(col_index, num)| {
-                actual_board.insert(num.clone(), (row_index.clone(), col_index.clone()));
-                marked_board.insert((row_index.clone(), col_index.clone()), false);
+                actual_board.insert(*num, (row_index, col_index));
+                marked_board.insert((row_index, col_index), false);
             })
         });
 
@@ -28,51 +28,28 @@ impl BingoBoard {
     }
 
     pub fn mark_value(&mut self, num: &i64) {
-        if self.actual_board.contains_key(&num) {
-            (*self.marked_board.get_mut(&self.actual_board[&num]).unwrap()) = true;
+        if let Some(position) = self.actual_board.get(num) {
+            self.marked_board.insert(*position, true);
         }
     }
 
     fn check_row_solved(&self, row_index: usize) -> bool {
-        let mut col_index: usize = 0;
-        while col_index < self.board_size {
-            if self.marked_board[&(row_index, col_index)] != true {
-                return false;
-            }
-            col_index += 1;
-        }
-
-        true
+        (0..self.board_size).all(|col_index| self.marked_board[&(row_index, col_index)])
     }
 
     fn check_col_solved(&self, col_index: usize) -> bool {
-        let mut row_index: usize = 0;
-        while row_index < self.board_size {
-            if self.marked_board[&(row_index, col_index)] != true {
-                return false;
-            }
-            row_index += 1;
-        }
-
-        true
+        (0..self.board_size).all(|row_index| self.marked_board[&(row_index, col_index)])
     }
 
     pub fn solved(&self) -> bool {
-        let mut index: usize = 0;
-        while index < self.board_size {
-            if self.check_row_solved(index) || self.check_col_solved(index) {
-                return true;
-            }
-            index += 1;
-        }
-
-        false
+        (0..self.board_size)
+            .any(|index| self.check_row_solved(index) || self.check_col_solved(index))
     }
 
     pub fn unmarked_total(&self) -> i64 {
         let mut total = 0;
         self.actual_board.iter().for_each(|(value, position)| {
-            if self.marked_board[position] == false {
+            if !self.marked_board[position] {
                 total += value;
             }
         });
@@ -85,11 +62,12 @@ impl Clone for BingoBoard {
         BingoBoard {
             actual_board: self.actual_board.clone(),
             marked_board: self.marked_board.clone(),
-            board_size: self.board_size.clone(),
+            board_size: self.board_size,
         }
     }
 }
 
+#[derive(Default)]
 pub struct Problem04 {}
 
 impl Problem04 {
@@ -97,40 +75,9 @@ impl Problem04 {
         Problem04 {}
     }
 
-    fn parse(&self, input: String) -> (Vec<i64>, Vec<BingoBoard>) {
-        let mut lines = input.lines();
-        let numbers_to_be_called: Vec<i64> = lines
-            .next()
-            .unwrap()
-            .split(',')
-            .map(|num| num.parse::<i64>().unwrap())
-            .collect();
-
-        let mut boards: Vec<BingoBoard> = Vec::new();
-        while !lines.next().is_none() {
-            let mut board_raw: Vec<&str> = vec![
-                lines.next().unwrap(),
-                lines.next().unwrap(),
-                lines.next().unwrap(),
-                lines.next().unwrap(),
-                lines.next().unwrap(),
-            ];
-            let board: Vec<Vec<i64>> = board_raw
-                .iter_mut()
-                .map(|row| {
-                    row.split_ascii_whitespace()
-                        .map(|val| val.parse::<i64>().unwrap())
-                        .collect()
-                })
-                .collect();
-            boards.push(BingoBoard::new(&board));
-        }
-        (numbers_to_be_called, boards)
-    }
-
-    fn solve_actual(&self, bingo_numbers: &Vec<i64>, bingo_boards: &mut Vec<BingoBoard>) -> i64 {
+    pub fn solve_actual(&self, bingo_numbers: &[i64], bingo_boards: &mut [BingoBoard]) -> i64 {
         for number in bingo_numbers {
-            for board in &mut *bingo_boards {
+            for board in bingo_boards.iter_mut() {
                 board.mark_value(number);
                 if board.solved() {
                     return number * board.unmarked_total();
@@ -141,15 +88,15 @@ impl Problem04 {
         0
     }
 
-    fn solve_actual_part2(
+    pub fn solve_actual_part2(
         &self,
-        bingo_numbers: &Vec<i64>,
-        bingo_boards: &mut Vec<BingoBoard>,
+        bingo_numbers: &[i64],
+        bingo_boards: &mut [BingoBoard],
     ) -> i64 {
         let mut last_win = 0;
         let mut solved_boards: HashSet<usize> = HashSet::new();
         for number in bingo_numbers {
-            for (board_index, board) in (&mut *bingo_boards).iter_mut().enumerate() {
+            for (board_index, board) in bingo_boards.iter_mut().enumerate() {
                 if solved_boards.contains(&board_index) {
                     continue;
                 }
@@ -167,23 +114,72 @@ impl Problem04 {
 }
 
 impl Problem for Problem04 {
+    type Parsed = (Vec<i64>, Vec<BingoBoard>);
+
     fn name(&self) -> &str {
-        "Day 4: Giant Squid"
+        Self::NAME
     }
 
-    fn solve(&self) -> i64 {
-        let input = get_input!("./inputs/problem_04.txt");
-        let (bingo_numbers, mut bingo_boards) = self.parse(input);
-        self.solve_actual(&bingo_numbers, &mut bingo_boards)
+    fn day(&self) -> u8 {
+        Self::DAY
     }
 
-    fn solve_part2(&self) -> (i64, Option<String>) {
-        let input = get_input!("./inputs/problem_04.txt");
-        let (bingo_numbers, mut bingo_boards) = self.parse(input);
-        (
-            self.solve_actual_part2(&bingo_numbers, &mut bingo_boards),
-            None,
-        )
+    fn default_input(&self) -> Result<String, AocError> {
+        get_input!("./inputs/problem_04.txt")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, AocError> {
+        let mut lines = input.lines().enumerate();
+        let numbers_line = match lines.next() {
+            Some((_, line)) if !line.is_empty() => line,
+            _ => return Err(AocError::parse(1, 1, "expected the numbers to be called")),
+        };
+        let numbers_to_be_called = numbers_line
+            .split(',')
+            .map(|num| parse_token(0, numbers_line, num))
+            .collect::<Result<Vec<i64>, AocError>>()?;
+
+        // Boards are separated by blank lines, and must be square.
+        let mut boards: Vec<BingoBoard> = Vec::new();
+        let mut board: Vec<Vec<i64>> = Vec::new();
+        let mut board_start = 0;
+        for (index, line) in lines.chain([(usize::MAX, "")]) {
+            if line.trim().is_empty() {
+                if !board.is_empty() {
+                    if board.iter().any(|row| row.len() != board.len()) {
+                        return Err(AocError::parse(
+                            board_start + 1,
+                            1,
+                            format!("expected a square board with {} rows", board.len()),
+                        ));
+                    }
+                    boards.push(BingoBoard::new(&board));
+                    board.clear();
+                }
+                continue;
+            }
+            if board.is_empty() {
+                board_start = index;
+            }
+            board.push(
+                line.split_ascii_whitespace()
+                    .map(|val| parse_token(index, line, val))
+                    .collect::<Result<Vec<i64>, AocError>>()?,
+            );
+        }
+        Ok((numbers_to_be_called, boards))
+    }
+
+    fn part1(&self, (bingo_numbers, bingo_boards): &Self::Parsed) -> Result<Answer, AocError> {
+        let mut bingo_boards = bingo_boards.clone();
+        Ok(self.solve_actual(bingo_numbers, &mut bingo_boards).into())
+    }
+
+    fn part2(&self, (bingo_numbers, bingo_boards): &Self::Parsed) -> Result<Answer, AocError> {
+        let mut bingo_boards = bingo_boards.clone();
+        Ok(self
+            .solve_actual_part2(bingo_numbers, &mut bingo_boards)
+            .into())
     }
 }
 
@@ -194,8 +190,8 @@ mod tests {
     #[test]
     fn test_solve_actual_from_example() {
         let problem = Problem04::new();
-        let input = get_input!("./inputs/problem_04_example.txt");
-        let (bingo_numbers, mut bingo_boards) = problem.parse(input);
+        let input = get_input!("./inputs/problem_04_example.txt").unwrap();
+        let (bingo_numbers, mut bingo_boards) = problem.parse(&input).unwrap();
         assert_eq!(
             problem.solve_actual(&bingo_numbers, &mut bingo_boards),
             4512
@@ -205,19 +201,33 @@ mod tests {
     #[test]
     fn test_solve_actual_from_input() {
         let problem = Problem04::new();
-        let input = get_input!("./inputs/problem_04.txt");
-        let (bingo_numbers, mut bingo_boards) = problem.parse(input);
+        let input = get_input!("./inputs/problem_04.txt").unwrap();
+        let (bingo_numbers, mut bingo_boards) = problem.parse(&input).unwrap();
         assert_eq!(
             problem.solve_actual(&bingo_numbers, &mut bingo_boards),
             58412
         );
     }
 
+    #[test]
+    fn test_parse_rejects_malformed_boards() {
+        let problem = Problem04::new();
+        let input = "7,4,9\n\n1 2\n3 4\n\n1 2 3\n4 5 6\n".to_string();
+        match problem.parse(&input) {
+            Err(AocError::Parse { line, .. }) => assert_eq!(line, 6),
+            other => panic!("expected a parse error, got {:?}", other.map(|p| p.0)),
+        }
+        match problem.parse("7,4,x\n\n1") {
+            Err(AocError::Parse { line, column, .. }) => assert_eq!((line, column), (1, 5)),
+            other => panic!("expected a parse error, got {:?}", other.map(|p| p.0)),
+        }
+    }
+
     #[test]
     fn test_solve_actual_part2_from_example() {
         let problem = Problem04::new();
-        let input = get_input!("./inputs/problem_04_example.txt");
-        let (bingo_numbers, mut bingo_boards) = problem.parse(input);
+        let input = get_input!("./inputs/problem_04_example.txt").unwrap();
+        let (bingo_numbers, mut bingo_boards) = problem.parse(&input).unwrap();
         assert_eq!(
             problem.solve_actual_part2(&bingo_numbers, &mut bingo_boards),
             1924
@@ -227,8 +237,8 @@ mod tests {
     #[test]
     fn test_solve_actual_part2_from_input() {
         let problem = Problem04::new();
-        let input = get_input!("./inputs/problem_04.txt");
-        let (bingo_numbers, mut bingo_boards) = problem.parse(input);
+        let input = get_input!("./inputs/problem_04.txt").unwrap();
+        let (bingo_numbers, mut bingo_boards) = problem.parse(&input).unwrap();
         assert_eq!(
             problem.solve_actual_part2(&bingo_numbers, &mut bingo_boards),
             10030
@@ -1,6 +1,11 @@
 use std::collections::HashSet;
 
-use crate::problem::Problem;
+use crate::{
+    answer::Answer,
+    error::AocError,
+    problem::Problem,
+    util::{column_of, parse_token},
+};
 
 pub struct Point {
     x: u32,
@@ -48,6 +53,7 @@ impl Line {
     }
 }
 
+#[derive(Default)]
 pub struct Problem05 {}
 
 impl Problem05 {
@@ -55,33 +61,21 @@ impl Problem05 {
         Problem05 {}
     }
 
-    fn parse(&self, input: String) -> Vec<Line> {
-        let mut submarine_lines: Vec<Line> = Vec::new();
-        input.lines().for_each(|line| {
-            let point_strs: Vec<&str> = line.split(" -> ").collect();
-            let start_pt: Vec<u32> = point_strs[0]
-                .split(",")
-                .map(|num| num.parse::<u32>().unwrap())
-                .collect();
-            let end_pt: Vec<u32> = point_strs[1]
-                .split(",")
-                .map(|num| num.parse::<u32>().unwrap())
-                .collect();
-            submarine_lines.push(Line {
-                start: Point {
-                    x: start_pt[0],
-                    y: start_pt[1],
-                },
-                end: Point {
-                    x: end_pt[0],
-                    y: end_pt[1],
-                },
-            });
-        });
-        submarine_lines
+    fn parse_point(&self, index: usize, line: &str, point: &str) -> Result<Point, AocError> {
+        match point.split_once(',') {
+            Some((x, y)) => Ok(Point {
+                x: parse_token(index, line, x)?,
+                y: parse_token(index, line, y)?,
+            }),
+            None => Err(AocError::parse(
+                index + 1,
+                column_of(line, point),
+                format!("expected a point like 'x,y', found '{}'", point),
+            )),
+        }
     }
 
-    fn solve_actual(&self, submarine_lines: &Vec<Line>, include_diagonals: bool) -> i64 {
+    pub fn solve_actual(&self, submarine_lines: &[Line], include_diagonals: bool) -> i64 {
         let mut seen_once: HashSet<u32> = HashSet::new();
         let mut seen_at_least_twice: HashSet<u32> = HashSet::new();
         submarine_lines.iter().for_each(|line| {
@@ -99,20 +93,40 @@ impl Problem05 {
 }
 
 impl Problem for Problem05 {
+    type Parsed = Vec<Line>;
+
     fn name(&self) -> &str {
-        "Day 5: Hydrothermal Venture"
+        Self::NAME
+    }
+
+    fn day(&self) -> u8 {
+        Self::DAY
+    }
+
+    fn default_input(&self) -> Result<String, AocError> {
+        get_input!("./inputs/problem_05.txt")
     }
 
-    fn solve(&self) -> i64 {
-        let input = get_input!("./inputs/problem_05.txt");
-        let submarine_lines = self.parse(input);
-        self.solve_actual(&submarine_lines, false)
+    fn parse(&self, input: &str) -> Result<Self::Parsed, AocError> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(index, line)| match line.split_once(" -> ") {
+                Some((start, end)) => Ok(Line {
+                    start: self.parse_point(index, line, start)?,
+                    end: self.parse_point(index, line, end)?,
+                }),
+                None => Err(AocError::parse(index + 1, 1, "expected 'x1,y1 -> x2,y2'")),
+            })
+            .collect()
     }
 
-    fn solve_part2(&self) -> (i64, Option<String>) {
-        let input = get_input!("./inputs/problem_05.txt");
-        let submarine_lines = self.parse(input);
-        (self.solve_actual(&submarine_lines, true), None)
+    fn part1(&self, submarine_lines: &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual(submarine_lines, false).into())
+    }
+
+    fn part2(&self, submarine_lines: &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual(submarine_lines, true).into())
     }
 }
 
@@ -123,32 +137,42 @@ mod tests {
     #[test]
     fn test_solve_actual_from_example() {
         let problem = Problem05::new();
-        let input = get_input!("./inputs/problem_05_example.txt");
-        let submarine_lines = problem.parse(input);
+        let input = get_input!("./inputs/problem_05_example.txt").unwrap();
+        let submarine_lines = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual(&submarine_lines, false), 5);
     }
 
     #[test]
     fn test_solve_actual_from_input() {
         let problem = Problem05::new();
-        let input = get_input!("./inputs/problem_05.txt");
-        let submarine_lines = problem.parse(input);
+        let input = get_input!("./inputs/problem_05.txt").unwrap();
+        let submarine_lines = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual(&submarine_lines, false), 4826);
     }
 
+    #[test]
+    fn test_parse_rejects_malformed_lines() {
+        let problem = Problem05::new();
+        match problem.parse("0,9 -> 5,9\n8,0 -> 0;8") {
+            Err(AocError::Parse { line, column, .. }) => assert_eq!((line, column), (2, 8)),
+            other => panic!("expected a parse error, got {:?}", other.map(|l| l.len())),
+        }
+        assert!(problem.parse("0,9 => 5,9").is_err());
+    }
+
     #[test]
     fn test_solve_actual_part2_from_example() {
         let problem = Problem05::new();
-        let input = get_input!("./inputs/problem_05_example.txt");
-        let submarine_lines = problem.parse(input);
+        let input = get_input!("./inputs/problem_05_example.txt").unwrap();
+        let submarine_lines = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual(&submarine_lines, true), 12);
     }
 
     #[test]
     fn test_solve_actual_part2_from_input() {
         let problem = Problem05::new();
-        let input = get_input!("./inputs/problem_05.txt");
-        let submarine_lines = problem.parse(input);
+        let input = get_input!("./inputs/problem_05.txt").unwrap();
+        let submarine_lines = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual(&submarine_lines, true), 16793);
     }
 }
@@ -1,5 +1,11 @@
-use crate::problem::Problem;
+use crate::{
+    answer::Answer,
+    error::AocError,
+    problem::Problem,
+    util::{column_of, parse_token},
+};
 
+#[derive(Default)]
 pub struct Problem06 {}
 
 impl Problem06 {
@@ -7,14 +13,7 @@ impl Problem06 {
         Problem06 {}
     }
 
-    fn parse(&self, input: String) -> Vec<i64> {
-        input
-            .split(',')
-            .map(|line| line.parse::<i64>().unwrap())
-            .collect()
-    }
-
-    fn solve_actual(&self, initial_fish: &Vec<i64>, simulation_days: i64) -> i64 {
+    pub fn solve_actual(&self, initial_fish: &[i64], simulation_days: i64) -> i64 {
         let mut fish_buckets: Vec<i64> = vec![0; 9];
         initial_fish
             .iter()
@@ -38,20 +37,40 @@ impl Problem06 {
 }
 
 impl Problem for Problem06 {
+    type Parsed = Vec<i64>;
+
     fn name(&self) -> &str {
-        "Day 6: Lanternfish"
+        Self::NAME
+    }
+
+    fn day(&self) -> u8 {
+        Self::DAY
+    }
+
+    fn default_input(&self) -> Result<String, AocError> {
+        get_input!("./inputs/problem_06.txt")
     }
 
-    fn solve(&self) -> i64 {
-        let input = get_input!("./inputs/problem_06.txt");
-        let initial_fish = self.parse(input);
-        self.solve_actual(&initial_fish, 80)
+    fn parse(&self, input: &str) -> Result<Self::Parsed, AocError> {
+        input
+            .split(',')
+            .map(|timer| match parse_token(0, input, timer)? {
+                valid @ 0..=8 => Ok(valid),
+                _ => Err(AocError::parse(
+                    1,
+                    column_of(input, timer),
+                    format!("timer '{}' is not between 0 and 8", timer),
+                )),
+            })
+            .collect()
+    }
+
+    fn part1(&self, initial_fish: &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual(initial_fish, 80).into())
     }
 
-    fn solve_part2(&self) -> (i64, Option<String>) {
-        let input = get_input!("./inputs/problem_06.txt");
-        let initial_fish = self.parse(input);
-        (self.solve_actual(&initial_fish, 256), None)
+    fn part2(&self, initial_fish: &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual(initial_fish, 256).into())
     }
 }
 
@@ -62,8 +81,8 @@ mod tests {
     #[test]
     fn test_solve_actual_from_example() {
         let problem = Problem06::new();
-        let input = get_input!("./inputs/problem_06_example.txt");
-        let initial_fish = problem.parse(input);
+        let input = get_input!("./inputs/problem_06_example.txt").unwrap();
+        let initial_fish = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual(&initial_fish, 18), 26);
         assert_eq!(problem.solve_actual(&initial_fish, 80), 5934);
     }
@@ -71,24 +90,34 @@ mod tests {
     #[test]
     fn test_solve_actual_from_input() {
         let problem = Problem06::new();
-        let input = get_input!("./inputs/problem_06.txt");
-        let initial_fish = problem.parse(input);
+        let input = get_input!("./inputs/problem_06.txt").unwrap();
+        let initial_fish = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual(&initial_fish, 80), 350917);
     }
 
+    #[test]
+    fn test_parse_rejects_bad_timers() {
+        let problem = Problem06::new();
+        match problem.parse("3,4,9,1") {
+            Err(AocError::Parse { line, column, .. }) => assert_eq!((line, column), (1, 5)),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+        assert!(problem.parse("3,4,,1").is_err());
+    }
+
     #[test]
     fn test_solve_actual_part2_from_example() {
         let problem = Problem06::new();
-        let input = get_input!("./inputs/problem_06_example.txt");
-        let initial_fish = problem.parse(input);
+        let input = get_input!("./inputs/problem_06_example.txt").unwrap();
+        let initial_fish = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual(&initial_fish, 256), 26984457539);
     }
 
     #[test]
     fn test_solve_actual_part2_from_input() {
         let problem = Problem06::new();
-        let input = get_input!("./inputs/problem_06.txt");
-        let initial_fish = problem.parse(input);
+        let input = get_input!("./inputs/problem_06.txt").unwrap();
+        let initial_fish = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual(&initial_fish, 256), 1592918715629);
     }
 }
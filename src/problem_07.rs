@@ -1,5 +1,6 @@
-use crate::problem::Problem;
+use crate::{answer::Answer, error::AocError, problem::Problem, util::parse_token};
 
+#[derive(Default)]
 pub struct Problem07 {}
 
 impl Problem07 {
@@ -7,14 +8,7 @@ impl Problem07 {
         Problem07 {}
     }
 
-    fn parse(&self, input: String) -> Vec<i64> {
-        input
-            .split(',')
-            .map(|line| line.parse::<i64>().unwrap())
-            .collect()
-    }
-
-    fn solve_actual(&self, crab_submarines: &Vec<i64>) -> i64 {
+    pub fn solve_actual(&self, crab_submarines: &[i64]) -> i64 {
         let mut positions = crab_submarines.to_owned();
         positions.sort();
         let ideal_position = positions[positions.len() / 2];
@@ -28,12 +22,11 @@ impl Problem07 {
         (num * (num + 1)) / 2
     }
 
-    fn solve_actual_part2(&self, crab_submarines: &Vec<i64>) -> i64 {
+    pub fn solve_actual_part2(&self, crab_submarines: &[i64]) -> i64 {
         let mut positions = crab_submarines.to_owned();
         positions.sort();
 
-        let ideal_position =
-            positions.iter().fold(0, |acc, crab| acc + crab) as i64 / positions.len() as i64;
+        let ideal_position = positions.iter().sum::<i64>() / positions.len() as i64;
 
         i64::min(
             positions.iter().fold(0, |acc, crab| {
@@ -47,20 +40,33 @@ impl Problem07 {
 }
 
 impl Problem for Problem07 {
+    type Parsed = Vec<i64>;
+
     fn name(&self) -> &str {
-        "Day 7: The Treachery of Whales"
+        Self::NAME
     }
 
-    fn solve(&self) -> i64 {
-        let input = get_input!("./inputs/problem_07.txt");
-        let crab_submarines = self.parse(input);
-        self.solve_actual(&crab_submarines)
+    fn day(&self) -> u8 {
+        Self::DAY
     }
 
-    fn solve_part2(&self) -> (i64, Option<String>) {
-        let input = get_input!("./inputs/problem_07.txt");
-        let crab_submarines = self.parse(input);
-        (self.solve_actual_part2(&crab_submarines), None)
+    fn default_input(&self) -> Result<String, AocError> {
+        get_input!("./inputs/problem_07.txt")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, AocError> {
+        input
+            .split(',')
+            .map(|position| parse_token(0, input, position))
+            .collect()
+    }
+
+    fn part1(&self, crab_submarines: &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual(crab_submarines).into())
+    }
+
+    fn part2(&self, crab_submarines: &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual_part2(crab_submarines).into())
     }
 }
 
@@ -71,32 +77,41 @@ mod tests {
     #[test]
     fn test_solve_actual_from_example() {
         let problem = Problem07::new();
-        let input = get_input!("./inputs/problem_07_example.txt");
-        let crab_submarines = problem.parse(input);
+        let input = get_input!("./inputs/problem_07_example.txt").unwrap();
+        let crab_submarines = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual(&crab_submarines), 37);
     }
 
     #[test]
     fn test_solve_actual_from_input() {
         let problem = Problem07::new();
-        let input = get_input!("./inputs/problem_07.txt");
-        let crab_submarines = problem.parse(input);
+        let input = get_input!("./inputs/problem_07.txt").unwrap();
+        let crab_submarines = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual(&crab_submarines), 342641);
     }
 
+    #[test]
+    fn test_parse_rejects_bad_positions() {
+        let problem = Problem07::new();
+        match problem.parse("16,1,-,0") {
+            Err(AocError::Parse { line, column, .. }) => assert_eq!((line, column), (1, 6)),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_solve_actual_part2_from_example() {
         let problem = Problem07::new();
-        let input = get_input!("./inputs/problem_07_example.txt");
-        let crab_submarines = problem.parse(input);
+        let input = get_input!("./inputs/problem_07_example.txt").unwrap();
+        let crab_submarines = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual_part2(&crab_submarines), 168);
     }
 
     #[test]
     fn test_solve_actual_part2_from_input() {
         let problem = Problem07::new();
-        let input = get_input!("./inputs/problem_07.txt");
-        let crab_submarines = problem.parse(input);
+        let input = get_input!("./inputs/problem_07.txt").unwrap();
+        let crab_submarines = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual_part2(&crab_submarines), 93006301);
     }
 }
@@ -1,13 +1,13 @@
 use std::collections::HashMap;
 
-use crate::problem::Problem;
+use crate::{answer::Answer, error::AocError, problem::Problem};
 
 pub struct SignalNote {
     signal_patterns: Vec<String>,
     output: Vec<String>,
 }
 
-fn pattern_to_bits(pattern: &String) -> u8 {
+fn pattern_to_bits(pattern: &str) -> u8 {
     let mut value = 0u8;
     pattern
         .chars()
@@ -108,6 +108,7 @@ impl SignalNote {
     }
 }
 
+#[derive(Default)]
 pub struct Problem08 {}
 
 impl Problem08 {
@@ -115,11 +116,7 @@ impl Problem08 {
         Problem08 {}
     }
 
-    fn parse(&self, input: String) -> Vec<SignalNote> {
-        input.lines().map(|line| SignalNote::new(line)).collect()
-    }
-
-    fn solve_actual(&self, signal_notes: &Vec<SignalNote>) -> i64 {
+    pub fn solve_actual(&self, signal_notes: &[SignalNote]) -> i64 {
         let mut total_easy_digits = 0;
         signal_notes.iter().for_each(|note| {
             note.output.iter().for_each(|digit| match digit.len() {
@@ -130,28 +127,59 @@ impl Problem08 {
         total_easy_digits
     }
 
-    fn solve_actual_part2(&self, signal_notes: &Vec<SignalNote>) -> i64 {
-        signal_notes
-            .iter()
-            .fold(0, |total, note| total + note.get_value())
+    pub fn solve_actual_part2(&self, signal_notes: &[SignalNote]) -> i64 {
+        signal_notes.iter().map(|note| note.get_value()).sum()
     }
 }
 
 impl Problem for Problem08 {
+    type Parsed = Vec<SignalNote>;
+
     fn name(&self) -> &str {
-        "Day 8: Seven Segment Search"
+        Self::NAME
+    }
+
+    fn day(&self) -> u8 {
+        Self::DAY
+    }
+
+    fn default_input(&self) -> Result<String, AocError> {
+        get_input!("./inputs/problem_08.txt")
     }
 
-    fn solve(&self) -> i64 {
-        let input = get_input!("./inputs/problem_08.txt");
-        let signal_notes = self.parse(input);
-        self.solve_actual(&signal_notes)
+    fn parse(&self, input: &str) -> Result<Self::Parsed, AocError> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(index, line)| {
+                let (patterns, output) = line.split_once(" | ").ok_or_else(|| {
+                    AocError::parse(index + 1, 1, "expected patterns and output split by ' | '")
+                })?;
+                if let Some(column) = line.find(|c| !matches!(c, 'a'..='g' | ' ' | '|')) {
+                    return Err(AocError::parse(
+                        index + 1,
+                        column + 1,
+                        "expected segments between 'a' and 'g'",
+                    ));
+                }
+                if patterns.split(' ').count() != 10 || output.split(' ').count() != 4 {
+                    return Err(AocError::parse(
+                        index + 1,
+                        1,
+                        "expected 10 signal patterns and 4 output digits",
+                    ));
+                }
+                Ok(SignalNote::new(line))
+            })
+            .collect()
+    }
+
+    fn part1(&self, signal_notes: &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual(signal_notes).into())
     }
 
-    fn solve_part2(&self) -> (i64, Option<String>) {
-        let input = get_input!("./inputs/problem_08.txt");
-        let signal_notes = self.parse(input);
-        (self.solve_actual_part2(&signal_notes), None)
+    fn part2(&self, signal_notes: &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual_part2(signal_notes).into())
     }
 }
 
@@ -161,46 +189,59 @@ mod tests {
 
     #[test]
     fn test_pattern_to_bits() {
-        assert_eq!(pattern_to_bits(&"a".to_string()), 1);
-        assert_eq!(pattern_to_bits(&"b".to_string()), 2);
-        assert_eq!(pattern_to_bits(&"c".to_string()), 4);
-        assert_eq!(pattern_to_bits(&"d".to_string()), 8);
-        assert_eq!(pattern_to_bits(&"e".to_string()), 16);
-        assert_eq!(pattern_to_bits(&"f".to_string()), 32);
-        assert_eq!(pattern_to_bits(&"g".to_string()), 64);
-        assert_eq!(pattern_to_bits(&"abcdefg".to_string()), 127);
-        assert_eq!(pattern_to_bits(&"aeg".to_string()), 81);
+        assert_eq!(pattern_to_bits("a"), 1);
+        assert_eq!(pattern_to_bits("b"), 2);
+        assert_eq!(pattern_to_bits("c"), 4);
+        assert_eq!(pattern_to_bits("d"), 8);
+        assert_eq!(pattern_to_bits("e"), 16);
+        assert_eq!(pattern_to_bits("f"), 32);
+        assert_eq!(pattern_to_bits("g"), 64);
+        assert_eq!(pattern_to_bits("abcdefg"), 127);
+        assert_eq!(pattern_to_bits("aeg"), 81);
     }
 
     #[test]
     fn test_solve_actual_from_example() {
         let problem = Problem08::new();
-        let input = get_input!("./inputs/problem_08_example.txt");
-        let signal_notes = problem.parse(input);
+        let input = get_input!("./inputs/problem_08_example.txt").unwrap();
+        let signal_notes = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual(&signal_notes), 26);
     }
 
     #[test]
     fn test_solve_actual_from_input() {
         let problem = Problem08::new();
-        let input = get_input!("./inputs/problem_08.txt");
-        let signal_notes = problem.parse(input);
+        let input = get_input!("./inputs/problem_08.txt").unwrap();
+        let signal_notes = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual(&signal_notes), 245);
     }
 
+    #[test]
+    fn test_parse_rejects_malformed_notes() {
+        let problem = Problem08::new();
+        let missing_output = "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab";
+        assert!(problem.parse(missing_output).is_err());
+        let bad_segment =
+            "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfxb cdbaf";
+        match problem.parse(bad_segment) {
+            Err(AocError::Parse { line, column, .. }) => assert_eq!((line, column), (1, 77)),
+            other => panic!("expected a parse error, got {:?}", other.map(|n| n.len())),
+        }
+    }
+
     #[test]
     fn test_solve_actual_part2_from_example() {
         let problem = Problem08::new();
-        let input = get_input!("./inputs/problem_08_example.txt");
-        let signal_notes = problem.parse(input);
+        let input = get_input!("./inputs/problem_08_example.txt").unwrap();
+        let signal_notes = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual_part2(&signal_notes), 61229);
     }
 
     #[test]
     fn test_solve_actual_part2_from_input() {
         let problem = Problem08::new();
-        let input = get_input!("./inputs/problem_08.txt");
-        let signal_notes = problem.parse(input);
+        let input = get_input!("./inputs/problem_08.txt").unwrap();
+        let signal_notes = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual_part2(&signal_notes), 983026);
     }
 }
@@ -1,7 +1,8 @@
 use std::collections::HashMap;
 
-use crate::problem::Problem;
+use crate::{answer::Answer, error::AocError, problem::Problem};
 
+#[derive(Default)]
 pub struct Problem10 {}
 
 impl Problem10 {
@@ -9,17 +10,7 @@ impl Problem10 {
         Problem10 {}
     }
 
-    fn parse(&self, input: String) -> Vec<String> {
-        input
-            .lines()
-            .map(|line| line.split_whitespace().collect())
-            .collect()
-    }
-
-    fn get_corrupt_and_incomplete(
-        &self,
-        navigation_subsystem: &Vec<String>,
-    ) -> (i64, Vec<Vec<char>>) {
+    fn get_corrupt_and_incomplete(&self, navigation_subsystem: &[String]) -> (i64, Vec<Vec<char>>) {
         let closed_map: HashMap<char, char> =
             HashMap::from_iter([(')', '('), ('}', '{'), (']', '['), ('>', '<')]);
         let scoring: HashMap<char, i64> =
@@ -36,11 +27,9 @@ impl Problem10 {
                 }
                 match c {
                     '(' | '[' | '{' | '<' => char_stack.push(c),
-                    ')' | ']' | '}' | '>' => {
-                        if char_stack.pop().unwrap() != closed_map[&c] {
-                            score += scoring[&c];
-                            corrupt = true;
-                        }
+                    ')' | ']' | '}' | '>' if char_stack.pop() != Some(closed_map[&c]) => {
+                        score += scoring[&c];
+                        corrupt = true;
                     }
                     _ => (),
                 }
@@ -52,12 +41,12 @@ impl Problem10 {
         (score, incomplete)
     }
 
-    fn solve_actual(&self, navigation_subsystem: &Vec<String>) -> i64 {
+    pub fn solve_actual(&self, navigation_subsystem: &[String]) -> i64 {
         let (corrupt, _incomplete) = self.get_corrupt_and_incomplete(navigation_subsystem);
         corrupt
     }
 
-    fn solve_actual_part2(&self, navigation_subsystem: &Vec<String>) -> i64 {
+    pub fn solve_actual_part2(&self, navigation_subsystem: &[String]) -> Result<i64, AocError> {
         let scoring: HashMap<char, i64> =
             HashMap::from_iter([('(', 1), ('[', 2), ('{', 3), ('<', 4)]);
 
@@ -67,27 +56,53 @@ impl Problem10 {
             .map(|left| left.iter().fold(0, |acc, c| (5 * acc) + scoring[c]))
             .collect();
 
+        if scores.is_empty() {
+            return Err(AocError::no_solution("every line is corrupted"));
+        }
         scores.sort();
 
-        scores[scores.len() / 2]
+        Ok(scores[scores.len() / 2])
     }
 }
 
 impl Problem for Problem10 {
+    type Parsed = Vec<String>;
+
     fn name(&self) -> &str {
-        "Day 10: Syntax Scoring"
+        Self::NAME
+    }
+
+    fn day(&self) -> u8 {
+        Self::DAY
+    }
+
+    fn default_input(&self) -> Result<String, AocError> {
+        get_input!("./inputs/problem_10.txt")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, AocError> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(index, line)| {
+                match line.find(|c: char| !"()[]{}<>".contains(c) && !c.is_whitespace()) {
+                    Some(column) => Err(AocError::parse(
+                        index + 1,
+                        column + 1,
+                        "expected only brackets",
+                    )),
+                    None => Ok(line.split_whitespace().collect()),
+                }
+            })
+            .collect()
     }
 
-    fn solve(&self) -> i64 {
-        let input = get_input!("./inputs/problem_10.txt");
-        let navigation_subsystem = self.parse(input);
-        self.solve_actual(&navigation_subsystem)
+    fn part1(&self, navigation_subsystem: &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual(navigation_subsystem).into())
     }
 
-    fn solve_part2(&self) -> (i64, Option<String>) {
-        let input = get_input!("./inputs/problem_10.txt");
-        let navigation_subsystem = self.parse(input);
-        (self.solve_actual_part2(&navigation_subsystem), None)
+    fn part2(&self, navigation_subsystem: &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual_part2(navigation_subsystem)?.into())
     }
 }
 
@@ -98,35 +113,66 @@ mod tests {
     #[test]
     fn test_solve_actual_from_example() {
         let problem = Problem10::new();
-        let input = get_input!("./inputs/problem_10_example.txt");
-        let navigation_subsystem = problem.parse(input);
+        let input = get_input!("./inputs/problem_10_example.txt").unwrap();
+        let navigation_subsystem = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual(&navigation_subsystem), 26397);
     }
 
     #[test]
     fn test_solve_actual_from_input() {
         let problem = Problem10::new();
-        let input = get_input!("./inputs/problem_10.txt");
-        let navigation_subsystem = problem.parse(input);
+        let input = get_input!("./inputs/problem_10.txt").unwrap();
+        let navigation_subsystem = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual(&navigation_subsystem), 318099);
     }
 
+    #[test]
+    fn test_parse_rejects_non_brackets() {
+        let problem = Problem10::new();
+        match problem.parse("[({(<(())[]>[[{[]{<()<>>\n[(()[<>])]({[<{<<a>>") {
+            Err(AocError::Parse { line, column, .. }) => assert_eq!((line, column), (2, 18)),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_solve_actual_unbalanced_closer_is_corrupt() {
+        let problem = Problem10::new();
+        let navigation_subsystem = problem.parse(")[]").unwrap();
+        assert_eq!(problem.solve_actual(&navigation_subsystem), 3);
+    }
+
     #[test]
     fn test_solve_actual_part2_from_example() {
         let problem = Problem10::new();
-        let input = get_input!("./inputs/problem_10_example.txt");
-        let navigation_subsystem = problem.parse(input);
-        assert_eq!(problem.solve_actual_part2(&navigation_subsystem), 288957);
+        let input = get_input!("./inputs/problem_10_example.txt").unwrap();
+        let navigation_subsystem = problem.parse(&input).unwrap();
+        assert_eq!(
+            problem.solve_actual_part2(&navigation_subsystem).unwrap(),
+            288957
+        );
     }
 
     #[test]
     fn test_solve_actual_part2_from_input() {
         let problem = Problem10::new();
-        let input = get_input!("./inputs/problem_10.txt");
-        let navigation_subsystem = problem.parse(input);
+        let input = get_input!("./inputs/problem_10.txt").unwrap();
+        let navigation_subsystem = problem.parse(&input).unwrap();
         assert_eq!(
-            problem.solve_actual_part2(&navigation_subsystem),
+            problem.solve_actual_part2(&navigation_subsystem).unwrap(),
             2389738699
         );
     }
+
+    #[test]
+    fn test_solve_actual_part2_needs_an_incomplete_line() {
+        let problem = Problem10::new();
+        for input in ["", "(]\n{()()()>"] {
+            let navigation_subsystem = problem.parse(input).unwrap();
+            assert!(matches!(
+                problem.solve_actual_part2(&navigation_subsystem),
+                Err(AocError::NoSolution { .. })
+            ));
+        }
+    }
 }
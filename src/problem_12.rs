@@ -1,6 +1,6 @@
 use std::collections::{HashMap, HashSet};
 
-use crate::problem::Problem;
+use crate::{answer::Answer, error::AocError, problem::Problem, util::column_of};
 
 /// Converts an input string to a nice integral value.
 ///
@@ -29,6 +29,7 @@ fn value_as_num(value: &str) -> u16 {
     }
 }
 
+#[derive(Default)]
 pub struct Problem12 {}
 
 impl Problem12 {
@@ -36,30 +37,6 @@ impl Problem12 {
         Problem12 {}
     }
 
-    fn parse(&self, input: String) -> HashMap<u16, Vec<u16>> {
-        let mut edges: HashMap<u16, Vec<u16>> = HashMap::new();
-        input.lines().for_each(|line| {
-            let nodes: Vec<&str> = line.split('-').collect();
-            let first = value_as_num(nodes[0]);
-            let second = value_as_num(nodes[1]);
-            if second != u16::MIN && first != u16::MAX {
-                if edges.contains_key(&first) {
-                    (*edges.get_mut(&first).unwrap()).push(second.clone());
-                } else {
-                    edges.insert(first.clone(), vec![second.clone()]);
-                }
-            }
-            if first != u16::MIN && second != u16::MAX {
-                if edges.contains_key(&second) {
-                    (*edges.get_mut(&second).unwrap()).push(first);
-                } else {
-                    edges.insert(second, vec![first]);
-                }
-            }
-        });
-        edges
-    }
-
     fn traverse_graph(
         &self,
         cave_paths: &HashMap<u16, Vec<u16>>,
@@ -69,13 +46,12 @@ impl Problem12 {
         let mut states_to_check: Vec<(u16, HashSet<u16>, bool)> = Vec::new();
         states_to_check.push((0, HashSet::new(), prevent_duplicate_small_node));
 
-        while states_to_check.len() > 0 {
-            let (current_node, mut seen_nodes, had_duplicate) = states_to_check.pop().unwrap();
+        while let Some((current_node, mut seen_nodes, had_duplicate)) = states_to_check.pop() {
             /* value of zz from value_as_num */
             if current_node > u16::MIN && current_node <= 6682 {
-                seen_nodes.insert(current_node.clone());
+                seen_nodes.insert(current_node);
             }
-            for node in &cave_paths[&current_node] {
+            for node in cave_paths.get(&current_node).into_iter().flatten() {
                 let seen = seen_nodes.contains(node);
                 if node == &u16::MAX {
                     paths += 1;
@@ -87,30 +63,68 @@ impl Problem12 {
         paths
     }
 
-    fn solve_actual(&self, cave_paths: &HashMap<u16, Vec<u16>>) -> i64 {
+    pub fn solve_actual(&self, cave_paths: &HashMap<u16, Vec<u16>>) -> i64 {
         self.traverse_graph(cave_paths, true)
     }
 
-    fn solve_actual_part2(&self, cave_paths: &HashMap<u16, Vec<u16>>) -> i64 {
+    pub fn solve_actual_part2(&self, cave_paths: &HashMap<u16, Vec<u16>>) -> i64 {
         self.traverse_graph(cave_paths, false)
     }
 }
 
 impl Problem for Problem12 {
+    type Parsed = HashMap<u16, Vec<u16>>;
+
     fn name(&self) -> &str {
-        "Day 12: Passage Pathing"
+        Self::NAME
+    }
+
+    fn day(&self) -> u8 {
+        Self::DAY
+    }
+
+    fn default_input(&self) -> Result<String, AocError> {
+        get_input!("./inputs/problem_12.txt")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, AocError> {
+        let mut edges: HashMap<u16, Vec<u16>> = HashMap::new();
+        for (index, line) in input.lines().enumerate() {
+            let (first, second) = line
+                .split_once('-')
+                .ok_or_else(|| AocError::parse(index + 1, 1, "expected an edge like 'A-b'"))?;
+            for node in [first, second] {
+                let valid = matches!(node, "start" | "end")
+                    || (matches!(node.len(), 1 | 2)
+                        && (node.chars().all(|c| c.is_ascii_lowercase())
+                            || node.chars().all(|c| c.is_ascii_uppercase())));
+                if !valid {
+                    return Err(AocError::parse(
+                        index + 1,
+                        column_of(line, node),
+                        format!("invalid cave name '{}'", node),
+                    ));
+                }
+            }
+
+            let first = value_as_num(first);
+            let second = value_as_num(second);
+            if second != u16::MIN && first != u16::MAX {
+                edges.entry(first).or_default().push(second);
+            }
+            if first != u16::MIN && second != u16::MAX {
+                edges.entry(second).or_default().push(first);
+            }
+        }
+        Ok(edges)
     }
 
-    fn solve(&self) -> i64 {
-        let input = get_input!("./inputs/problem_12.txt");
-        let cave_paths = self.parse(input);
-        self.solve_actual(&cave_paths)
+    fn part1(&self, cave_paths: &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual(cave_paths).into())
     }
 
-    fn solve_part2(&self) -> (i64, Option<String>) {
-        let input = get_input!("./inputs/problem_12.txt");
-        let cave_paths = self.parse(input);
-        (self.solve_actual_part2(&cave_paths), None)
+    fn part2(&self, cave_paths: &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual_part2(cave_paths).into())
     }
 }
 
@@ -121,64 +135,81 @@ mod tests {
     #[test]
     fn test_solve_actual_from_example_01() {
         let problem = Problem12::new();
-        let input = get_input!("./inputs/problem_12_example_01.txt");
-        let cave_paths = problem.parse(input);
+        let input = get_input!("./inputs/problem_12_example_01.txt").unwrap();
+        let cave_paths = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual(&cave_paths), 10);
     }
 
     #[test]
     fn test_solve_actual_from_example_02() {
         let problem = Problem12::new();
-        let input = get_input!("./inputs/problem_12_example_02.txt");
-        let cave_paths = problem.parse(input);
+        let input = get_input!("./inputs/problem_12_example_02.txt").unwrap();
+        let cave_paths = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual(&cave_paths), 19);
     }
 
     #[test]
     fn test_solve_actual_from_example_03() {
         let problem = Problem12::new();
-        let input = get_input!("./inputs/problem_12_example_03.txt");
-        let cave_paths = problem.parse(input);
+        let input = get_input!("./inputs/problem_12_example_03.txt").unwrap();
+        let cave_paths = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual(&cave_paths), 226);
     }
 
     #[test]
     fn test_solve_actual_from_input() {
         let problem = Problem12::new();
-        let input = get_input!("./inputs/problem_12.txt");
-        let cave_paths = problem.parse(input);
+        let input = get_input!("./inputs/problem_12.txt").unwrap();
+        let cave_paths = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual(&cave_paths), 4186);
     }
 
+    #[test]
+    fn test_parse_rejects_malformed_edges() {
+        let problem = Problem12::new();
+        assert!(problem.parse("start-A\nA end").is_err());
+        match problem.parse("start-A\nA-bcd") {
+            Err(AocError::Parse { line, column, .. }) => assert_eq!((line, column), (2, 3)),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_solve_actual_dead_end_cave() {
+        let problem = Problem12::new();
+        let cave_paths = problem.parse("start-A\nstart-b\nA-end").unwrap();
+        assert_eq!(problem.solve_actual(&cave_paths), 1);
+    }
+
     #[test]
     fn test_solve_actual_part2_from_example_01() {
         let problem = Problem12::new();
-        let input = get_input!("./inputs/problem_12_example_01.txt");
-        let cave_paths = problem.parse(input);
+        let input = get_input!("./inputs/problem_12_example_01.txt").unwrap();
+        let cave_paths = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual_part2(&cave_paths), 36);
     }
 
     #[test]
     fn test_solve_actual_part2_from_example_02() {
         let problem = Problem12::new();
-        let input = get_input!("./inputs/problem_12_example_02.txt");
-        let cave_paths = problem.parse(input);
+        let input = get_input!("./inputs/problem_12_example_02.txt").unwrap();
+        let cave_paths = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual_part2(&cave_paths), 103);
     }
 
     #[test]
     fn test_solve_actual_part2_from_example_03() {
         let problem = Problem12::new();
-        let input = get_input!("./inputs/problem_12_example_03.txt");
-        let cave_paths = problem.parse(input);
+        let input = get_input!("./inputs/problem_12_example_03.txt").unwrap();
+        let cave_paths = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual_part2(&cave_paths), 3509);
     }
 
     #[test]
     fn test_solve_actual_part2_from_input() {
         let problem = Problem12::new();
-        let input = get_input!("./inputs/problem_12.txt");
-        let cave_paths = problem.parse(input);
+        let input = get_input!("./inputs/problem_12.txt").unwrap();
+        let cave_paths = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual_part2(&cave_paths), 92111);
     }
 }
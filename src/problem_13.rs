@@ -1,8 +1,17 @@
 use std::collections::{HashSet, VecDeque};
 
-use crate::problem::Problem;
+use crate::{
+    answer::Answer,
+    error::AocError,
+    ocr,
+    problem::Problem,
+    util::{column_of, parse_token},
+};
 
-enum FoldDirection {
+/// Which way to fold: `Horizontal` folds the bottom half up along a line
+/// `y = position`, `Vertical` folds the right half left along `x = position`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FoldDirection {
     Horizontal,
     Vertical,
 }
@@ -11,6 +20,17 @@ pub struct FoldInstruction {
     direction: FoldDirection,
     position: u16,
 }
+
+impl FoldInstruction {
+    pub fn new(direction: FoldDirection, position: u16) -> FoldInstruction {
+        FoldInstruction {
+            direction,
+            position,
+        }
+    }
+}
+
+#[derive(Clone, Default)]
 pub struct TransparentPaper {
     dots: HashSet<(u16, u16)>,
     x_max: u16,
@@ -61,24 +81,29 @@ impl TransparentPaper {
         self.dots = new_dots;
     }
 
+    pub fn read_letters(&self) -> Result<String, AocError> {
+        ocr::recognize(&self.rows())
+    }
+
     pub fn visible_dots(&self) -> i64 {
         self.dots.len() as i64
     }
 
-    pub fn pretty(&self) -> String {
-        let mut output: Vec<String> = vec!["           ".to_string(); self.y_max as usize];
-        for row in 0..self.y_max {
-            for col in 0..self.x_max {
-                output[row as usize].push_str(match self.dots.contains(&(col, row)) {
-                    true => "#",
-                    false => ".",
-                });
-            }
-        }
-        output.join("\n")
+    pub fn rows(&self) -> Vec<String> {
+        (0..self.y_max)
+            .map(|row| {
+                (0..self.x_max)
+                    .map(|col| match self.dots.contains(&(col, row)) {
+                        true => '#',
+                        false => '.',
+                    })
+                    .collect()
+            })
+            .collect()
     }
 }
 
+#[derive(Default)]
 pub struct Problem13 {}
 
 impl Problem13 {
@@ -86,37 +111,7 @@ impl Problem13 {
         Problem13 {}
     }
 
-    fn parse(&self, input: String) -> (TransparentPaper, VecDeque<FoldInstruction>) {
-        let mut paper = TransparentPaper::new();
-        let mut instructions = VecDeque::new();
-        let mut lines = input.lines();
-
-        while let Some(line) = lines.next() {
-            if line.len() == 0 {
-                break;
-            }
-            let nums = line.split(',').collect::<Vec<&str>>();
-            paper.add(
-                nums[0].parse::<u16>().unwrap(),
-                nums[1].parse::<u16>().unwrap(),
-            );
-        }
-
-        while let Some(line) = lines.next() {
-            let nums = line.split('=').collect::<Vec<&str>>();
-            instructions.push_back(FoldInstruction {
-                direction: match nums[0].ends_with('y') {
-                    true => FoldDirection::Horizontal,
-                    false => FoldDirection::Vertical,
-                },
-                position: nums[1].parse::<u16>().unwrap(),
-            });
-        }
-
-        (paper, instructions)
-    }
-
-    fn solve_actual(
+    pub fn solve_actual(
         &self,
         paper: &mut TransparentPaper,
         instructions: &VecDeque<FoldInstruction>,
@@ -125,7 +120,7 @@ impl Problem13 {
         paper.visible_dots()
     }
 
-    fn solve_actual_part2(
+    pub fn solve_actual_part2(
         &self,
         paper: &mut TransparentPaper,
         instructions: &VecDeque<FoldInstruction>,
@@ -138,23 +133,78 @@ impl Problem13 {
 }
 
 impl Problem for Problem13 {
+    type Parsed = (TransparentPaper, VecDeque<FoldInstruction>);
+
     fn name(&self) -> &str {
-        "Day 13: Transparent Origami"
+        Self::NAME
     }
 
-    fn solve(&self) -> i64 {
-        let input = get_input!("./inputs/problem_13.txt");
-        let (mut paper, instructions) = self.parse(input);
-        self.solve_actual(&mut paper, &instructions)
+    fn day(&self) -> u8 {
+        Self::DAY
     }
 
-    fn solve_part2(&self) -> (i64, Option<String>) {
-        let input = get_input!("./inputs/problem_13.txt");
-        let (mut paper, instructions) = self.parse(input);
-        (
-            self.solve_actual_part2(&mut paper, &instructions),
-            Some(paper.pretty()),
-        )
+    fn default_input(&self) -> Result<String, AocError> {
+        get_input!("./inputs/problem_13.txt")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, AocError> {
+        let mut paper = TransparentPaper::new();
+        let mut instructions = VecDeque::new();
+        let mut lines = input.lines().enumerate();
+
+        for (index, line) in lines.by_ref() {
+            if line.is_empty() {
+                break;
+            }
+            let (x, y) = line
+                .split_once(',')
+                .ok_or_else(|| AocError::parse(index + 1, 1, "expected a dot like 'x,y'"))?;
+            paper.add(parse_token(index, line, x)?, parse_token(index, line, y)?);
+        }
+
+        for (index, line) in lines {
+            let (axis, position) = line
+                .strip_prefix("fold along ")
+                .and_then(|fold| fold.split_once('='))
+                .ok_or_else(|| {
+                    AocError::parse(index + 1, 1, "expected a fold like 'fold along y=7'")
+                })?;
+            let direction = match axis {
+                "y" => FoldDirection::Horizontal,
+                "x" => FoldDirection::Vertical,
+                _ => {
+                    return Err(AocError::parse(
+                        index + 1,
+                        column_of(line, axis),
+                        format!("unknown fold axis '{}'", axis),
+                    ))
+                }
+            };
+            instructions.push_back(FoldInstruction::new(
+                direction,
+                parse_token(index, line, position)?,
+            ));
+        }
+
+        if instructions.is_empty() {
+            return Err(AocError::parse(
+                input.lines().count() + 1,
+                1,
+                "expected at least one fold instruction",
+            ));
+        }
+        Ok((paper, instructions))
+    }
+
+    fn part1(&self, (paper, instructions): &Self::Parsed) -> Result<Answer, AocError> {
+        let mut paper = paper.clone();
+        Ok(self.solve_actual(&mut paper, instructions).into())
+    }
+
+    fn part2(&self, (paper, instructions): &Self::Parsed) -> Result<Answer, AocError> {
+        let mut paper = paper.clone();
+        self.solve_actual_part2(&mut paper, instructions);
+        Ok(paper.read_letters()?.into())
     }
 }
 
@@ -165,40 +215,72 @@ mod tests {
     #[test]
     fn test_solve_actual_from_example() {
         let problem = Problem13::new();
-        let input = get_input!("./inputs/problem_13_example.txt");
-        let (mut paper, instructions) = problem.parse(input);
+        let input = get_input!("./inputs/problem_13_example.txt").unwrap();
+        let (mut paper, instructions) = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual(&mut paper, &instructions), 17);
     }
 
     #[test]
     fn test_solve_actual_from_input() {
         let problem = Problem13::new();
-        let input = get_input!("./inputs/problem_13.txt");
-        let (mut paper, instructions) = problem.parse(input);
+        let input = get_input!("./inputs/problem_13.txt").unwrap();
+        let (mut paper, instructions) = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual(&mut paper, &instructions), 775);
     }
 
+    #[test]
+    fn test_fold_built_by_hand() {
+        let mut paper = TransparentPaper::new();
+        paper.add(0, 0);
+        paper.add(4, 0);
+        paper.add(1, 2);
+        paper.fold(&FoldInstruction::new(FoldDirection::Vertical, 2));
+        assert_eq!(paper.visible_dots(), 2);
+        paper.fold(&FoldInstruction::new(FoldDirection::Horizontal, 1));
+        assert_eq!(paper.visible_dots(), 2);
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_instructions() {
+        let problem = Problem13::new();
+        assert!(problem.parse("6,10\n0,14\n").is_err());
+        match problem.parse("6,10\n\nfold along z=7") {
+            Err(AocError::Parse { line, column, .. }) => assert_eq!((line, column), (3, 12)),
+            other => panic!("expected a parse error, got {:?}", other.map(|p| p.1.len())),
+        }
+        match problem.parse("6,10\n0;14\n\nfold along y=7") {
+            Err(AocError::Parse { line, .. }) => assert_eq!(line, 2),
+            other => panic!("expected a parse error, got {:?}", other.map(|p| p.1.len())),
+        }
+    }
+
     #[test]
     fn test_solve_actual_part2_from_example() {
         let problem = Problem13::new();
-        let input = get_input!("./inputs/problem_13_example.txt");
-        let (mut paper, instructions) = problem.parse(input);
+        let input = get_input!("./inputs/problem_13_example.txt").unwrap();
+        let (mut paper, instructions) = problem.parse(&input).unwrap();
         problem.solve_actual_part2(&mut paper, &instructions);
         assert_eq!(
-            paper.pretty().split(' ').collect::<Vec<&str>>().join(""),
+            paper.rows().join("\n"),
             "#####\n#...#\n#...#\n#...#\n#####\n.....\n....."
         );
+        // The example folds into a square, which isn't a letter.
+        match paper.read_letters() {
+            Err(AocError::UnrecognizedGlyphs { positions, .. }) => assert_eq!(positions, vec![0]),
+            other => panic!("expected unrecognized glyphs, got {:?}", other),
+        }
     }
 
     #[test]
     fn test_solve_actual_part2_from_input() {
         let problem = Problem13::new();
-        let input = get_input!("./inputs/problem_13.txt");
-        let (mut paper, instructions) = problem.parse(input);
+        let input = get_input!("./inputs/problem_13.txt").unwrap();
+        let (mut paper, instructions) = problem.parse(&input).unwrap();
         problem.solve_actual_part2(&mut paper, &instructions);
         assert_eq!(
-            paper.pretty().split(' ').collect::<Vec<&str>>().join(""),
+            paper.rows().join("\n"),
             "###..####.#..#.###..#..#.###..#..#.###..\n#..#.#....#..#.#..#.#..#.#..#.#.#..#..#.\n#..#.###..#..#.#..#.#..#.#..#.##...#..#.\n###..#....#..#.###..#..#.###..#.#..###..\n#.#..#....#..#.#....#..#.#....#.#..#.#..\n#..#.####..##..#.....##..#....#..#.#..#."
         );
+        assert_eq!(paper.read_letters().unwrap(), "REUPUPKR");
     }
 }
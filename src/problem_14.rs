@@ -1,6 +1,6 @@
-use std::collections::HashMap;
+use std::collections::{hash_map::Entry, HashMap};
 
-use crate::problem::Problem;
+use crate::{answer::Answer, error::AocError, problem::Problem};
 
 /// The largest string we'd expect to see is 2 characters, and each character
 /// can only be one of 26 values, so at most needs 5 bits to be represented.
@@ -20,14 +20,16 @@ pub trait EasyUpdate<K, V> {
 
 impl<K: Eq + core::hash::Hash, V: std::ops::AddAssign> EasyUpdate<K, V> for HashMap<K, V> {
     fn update(&mut self, k: K, v: V) {
-        if self.contains_key(&k) {
-            (*self.get_mut(&k).unwrap()) += v;
-        } else {
-            self.insert(k, v);
+        match self.entry(k) {
+            Entry::Occupied(mut entry) => *entry.get_mut() += v,
+            Entry::Vacant(entry) => {
+                entry.insert(v);
+            }
         }
     }
 }
 
+#[derive(Default)]
 pub struct Problem14 {}
 
 impl Problem14 {
@@ -35,21 +37,9 @@ impl Problem14 {
         Problem14 {}
     }
 
-    fn parse(&self, input: String) -> (String, HashMap<u16, u16>) {
-        let mut pair_rules: HashMap<u16, u16> = HashMap::new();
-        let mut lines = input.lines();
-        let polymer_template = lines.next().unwrap().to_string();
-        lines.next();
-        while let Some(rule) = lines.next() {
-            let components = rule.split(" -> ").collect::<Vec<&str>>();
-            pair_rules.insert(str_to_num(components[0]), str_to_num(components[1]));
-        }
-        (polymer_template, pair_rules)
-    }
-
     fn build_char_map(
         &self,
-        polymer_template: &String,
+        polymer_template: &str,
         pair_rules: &HashMap<u16, u16>,
     ) -> HashMap<u16, i64> {
         let mut char_count = HashMap::new();
@@ -72,11 +62,10 @@ impl Problem14 {
         char_count
     }
 
-    fn build_polymer_pairs(&self, polymer_template: &String) -> HashMap<u16, i64> {
+    fn build_polymer_pairs(&self, polymer_template: &str) -> HashMap<u16, i64> {
         let mut pair_map = HashMap::new();
         let polymer_chars = polymer_template.chars().collect::<Vec<char>>();
-        let mut window = polymer_chars.windows(2);
-        while let Some(chars) = window.next() {
+        for chars in polymer_chars.windows(2) {
             let pair = str_to_num(chars.iter().collect::<String>().as_str());
             pair_map.update(pair, 1);
         }
@@ -85,7 +74,7 @@ impl Problem14 {
 
     fn do_polymerization(
         &self,
-        polymer_template: &String,
+        polymer_template: &str,
         pair_rules: &HashMap<u16, u16>,
         steps: u8,
     ) -> i64 {
@@ -120,33 +109,81 @@ impl Problem14 {
         char_count.values().max().unwrap() - char_count.values().min().unwrap()
     }
 
-    fn solve_actual(&self, polymer_template: &String, pair_rules: &HashMap<u16, u16>) -> i64 {
+    pub fn solve_actual(&self, polymer_template: &str, pair_rules: &HashMap<u16, u16>) -> i64 {
         self.do_polymerization(polymer_template, pair_rules, 10)
     }
 
-    fn solve_actual_part2(&self, polymer_template: &String, pair_rules: &HashMap<u16, u16>) -> i64 {
+    pub fn solve_actual_part2(
+        &self,
+        polymer_template: &str,
+        pair_rules: &HashMap<u16, u16>,
+    ) -> i64 {
         self.do_polymerization(polymer_template, pair_rules, 40)
     }
 }
 
 impl Problem for Problem14 {
+    type Parsed = (String, HashMap<u16, u16>);
+
     fn name(&self) -> &str {
-        "Day 14: Extended Polymerization"
+        Self::NAME
     }
 
-    fn solve(&self) -> i64 {
-        let input = get_input!("./inputs/problem_14.txt");
-        let (polymer_template, pair_rules) = self.parse(input);
-        self.solve_actual(&polymer_template, &pair_rules)
+    fn day(&self) -> u8 {
+        Self::DAY
     }
 
-    fn solve_part2(&self) -> (i64, Option<String>) {
-        let input = get_input!("./inputs/problem_14.txt");
-        let (polymer_template, pair_rules) = self.parse(input);
-        (
-            self.solve_actual_part2(&polymer_template, &pair_rules),
-            None,
-        )
+    fn default_input(&self) -> Result<String, AocError> {
+        get_input!("./inputs/problem_14.txt")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, AocError> {
+        let mut pair_rules: HashMap<u16, u16> = HashMap::new();
+        let mut lines = input.lines().enumerate();
+        let polymer_template = match lines.next() {
+            Some((_, line)) if !line.is_empty() => line.to_string(),
+            _ => return Err(AocError::parse(1, 1, "expected a polymer template")),
+        };
+        if let Some(column) = polymer_template.find(|c: char| !c.is_ascii_uppercase()) {
+            return Err(AocError::parse(
+                1,
+                column + 1,
+                "expected an uppercase element",
+            ));
+        }
+        lines.next();
+
+        for (index, rule) in lines {
+            let valid = rule.split_once(" -> ").filter(|(pair, element)| {
+                pair.len() == 2
+                    && element.len() == 1
+                    && pair
+                        .chars()
+                        .chain(element.chars())
+                        .all(|c| c.is_ascii_uppercase())
+            });
+            match valid {
+                Some((pair, element)) => {
+                    pair_rules.insert(str_to_num(pair), str_to_num(element));
+                }
+                None => {
+                    return Err(AocError::parse(
+                        index + 1,
+                        1,
+                        "expected a rule like 'CH -> B'",
+                    ))
+                }
+            }
+        }
+        Ok((polymer_template, pair_rules))
+    }
+
+    fn part1(&self, (polymer_template, pair_rules): &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual(polymer_template, pair_rules).into())
+    }
+
+    fn part2(&self, (polymer_template, pair_rules): &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual_part2(polymer_template, pair_rules).into())
     }
 }
 
@@ -157,24 +194,34 @@ mod tests {
     #[test]
     fn test_solve_actual_from_example() {
         let problem = Problem14::new();
-        let input = get_input!("./inputs/problem_14_example.txt");
-        let (polymer_template, pair_rules) = problem.parse(input);
+        let input = get_input!("./inputs/problem_14_example.txt").unwrap();
+        let (polymer_template, pair_rules) = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual(&polymer_template, &pair_rules), 1588);
     }
 
     #[test]
     fn test_solve_actual_from_input() {
         let problem = Problem14::new();
-        let input = get_input!("./inputs/problem_14.txt");
-        let (polymer_template, pair_rules) = problem.parse(input);
+        let input = get_input!("./inputs/problem_14.txt").unwrap();
+        let (polymer_template, pair_rules) = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual(&polymer_template, &pair_rules), 2745);
     }
 
+    #[test]
+    fn test_parse_rejects_malformed_rules() {
+        let problem = Problem14::new();
+        assert!(problem.parse("NnCB\n\nCH -> B").is_err());
+        match problem.parse("NNCB\n\nCH -> B\nHH => N") {
+            Err(AocError::Parse { line, .. }) => assert_eq!(line, 4),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_solve_actual_part2_from_example() {
         let problem = Problem14::new();
-        let input = get_input!("./inputs/problem_14_example.txt");
-        let (polymer_template, pair_rules) = problem.parse(input);
+        let input = get_input!("./inputs/problem_14_example.txt").unwrap();
+        let (polymer_template, pair_rules) = problem.parse(&input).unwrap();
         assert_eq!(
             problem.solve_actual_part2(&polymer_template, &pair_rules),
             2188189693529
@@ -184,8 +231,8 @@ mod tests {
     #[test]
     fn test_solve_actual_part2_from_input() {
         let problem = Problem14::new();
-        let input = get_input!("./inputs/problem_14.txt");
-        let (polymer_template, pair_rules) = problem.parse(input);
+        let input = get_input!("./inputs/problem_14.txt").unwrap();
+        let (polymer_template, pair_rules) = problem.parse(&input).unwrap();
         assert_eq!(
             problem.solve_actual_part2(&polymer_template, &pair_rules),
             3420801168962
@@ -1,29 +1,6 @@
-use std::{
-    cmp::Reverse,
-    collections::{BinaryHeap, HashSet},
-};
-
-use crate::problem::Problem;
-
-fn neighbors(row: usize, col: usize, size: usize) -> Vec<(usize, usize)> {
-    let mut neighbors = Vec::new();
-
-    if row > 0 {
-        neighbors.push((row - 1, col));
-    }
-    if col > 0 {
-        neighbors.push((row, col - 1));
-    }
-    if row < size - 1 {
-        neighbors.push((row + 1, col));
-    }
-    if col < size - 1 {
-        neighbors.push((row, col + 1));
-    }
-
-    neighbors
-}
+use crate::{answer::Answer, error::AocError, grid::Grid, problem::Problem, search::dijkstra_indexed};
 
+#[derive(Default)]
 pub struct Problem15 {}
 
 impl Problem15 {
@@ -31,31 +8,17 @@ impl Problem15 {
         Problem15 {}
     }
 
-    fn parse(&self, input: String) -> Vec<Vec<i64>> {
-        let mut grid = Vec::new();
-        input.lines().for_each(|line| {
-            grid.push(
-                line.chars()
-                    .map(|c| c.to_digit(10).unwrap() as i64)
-                    .collect::<Vec<i64>>(),
-            )
-        });
-        grid
-    }
-
-    fn expand_grid(&self, risk_levels: &Vec<Vec<i64>>) -> Vec<Vec<i64>> {
-        let mut new_grid = vec![vec![-1; risk_levels.len() * 5]; risk_levels.len() * 5];
-
-        for row in 0..risk_levels.len() {
-            for col in 0..risk_levels.len() {
-                for nrow in 0..5usize {
-                    for ncol in 0..5usize {
-                        let new_risk = risk_levels[row][col] + nrow as i64 + ncol as i64;
-                        new_grid[(nrow * risk_levels.len()) + row]
-                            [(ncol * risk_levels.len()) + col] = match new_risk > 9 {
-                            true => new_risk % 10 + 1,
-                            false => new_risk,
-                        }
+    fn expand_grid(&self, risk_levels: &Grid<i64>) -> Grid<i64> {
+        let (width, height) = (risk_levels.width(), risk_levels.height());
+        let mut new_grid = Grid::new(width * 5, height * 5, -1);
+
+        for ((row, col), risk) in risk_levels.iter() {
+            for nrow in 0..5usize {
+                for ncol in 0..5usize {
+                    let new_risk = risk + nrow as i64 + ncol as i64;
+                    new_grid[(nrow * height + row, ncol * width + col)] = match new_risk > 9 {
+                        true => new_risk % 10 + 1,
+                        false => new_risk,
                     }
                 }
             }
@@ -64,59 +27,58 @@ impl Problem15 {
         new_grid
     }
 
-    fn get_lowest_risk_cost(&self, risk_levels: &Vec<Vec<i64>>) -> i64 {
-        let mut visited: HashSet<usize> = HashSet::new();
-        let mut estimated_costs: Vec<Vec<i64>> =
-            vec![vec![i64::MAX; risk_levels.len()]; risk_levels.len()];
-        estimated_costs[0][0] = 0;
-
-        // Need to use Reverse<> to act as a min-heap
-        let mut to_visit: BinaryHeap<(Reverse<i64>, usize, usize)> = BinaryHeap::new();
-        to_visit.push((Reverse(0), 0, 0));
-
-        while let Some((_score, row, col)) = to_visit.pop() {
-            visited.insert(row * risk_levels.len() + col);
-
-            for (nrow, ncol) in neighbors(row, col, risk_levels.len()) {
-                let poshash = nrow * risk_levels.len() + ncol;
-                if visited.contains(&poshash) {
-                    continue;
-                }
-                let new_cost = estimated_costs[row][col] + risk_levels[nrow][ncol];
-                if new_cost < estimated_costs[nrow][ncol] {
-                    estimated_costs[nrow][ncol] = new_cost;
-                    to_visit.push((Reverse(new_cost), nrow, ncol));
-                }
-            }
-        }
-
-        estimated_costs[risk_levels.len() - 1][risk_levels.len() - 1]
-    }
-
-    fn solve_actual(&self, risk_levels: &Vec<Vec<i64>>) -> i64 {
+    fn get_lowest_risk_cost(&self, risk_levels: &Grid<i64>) -> i64 {
+        let (width, height) = (risk_levels.width(), risk_levels.height());
+        let goal = (height - 1, width - 1);
+        dijkstra_indexed(
+            (0, 0),
+            width * height,
+            |(row, col)| row * width + col,
+            |position| {
+                risk_levels
+                    .neighbors(*position)
+                    .map(|neighbor| (neighbor, risk_levels[neighbor]))
+            },
+            |position| *position == goal,
+        )
+        .expect("every cell of the grid is reachable")
+        .cost
+    }
+
+    pub fn solve_actual(&self, risk_levels: &Grid<i64>) -> i64 {
         self.get_lowest_risk_cost(risk_levels)
     }
 
-    fn solve_actual_part2(&self, risk_levels: &Vec<Vec<i64>>) -> i64 {
+    pub fn solve_actual_part2(&self, risk_levels: &Grid<i64>) -> i64 {
         self.get_lowest_risk_cost(&self.expand_grid(risk_levels))
     }
 }
 
 impl Problem for Problem15 {
+    type Parsed = Grid<i64>;
+
     fn name(&self) -> &str {
-        "Day 15: Chiton"
+        Self::NAME
+    }
+
+    fn day(&self) -> u8 {
+        Self::DAY
+    }
+
+    fn default_input(&self) -> Result<String, AocError> {
+        get_input!("./inputs/problem_15.txt")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, AocError> {
+        Ok(Grid::parse_digits(input)?.map(|risk| i64::from(*risk)))
     }
 
-    fn solve(&self) -> i64 {
-        let input = get_input!("./inputs/problem_15.txt");
-        let risk_levels = self.parse(input);
-        self.solve_actual(&risk_levels)
+    fn part1(&self, risk_levels: &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual(risk_levels).into())
     }
 
-    fn solve_part2(&self) -> (i64, Option<String>) {
-        let input = get_input!("./inputs/problem_15.txt");
-        let risk_levels = self.parse(input);
-        (self.solve_actual_part2(&risk_levels), None)
+    fn part2(&self, risk_levels: &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual_part2(risk_levels).into())
     }
 }
 
@@ -127,32 +89,43 @@ mod tests {
     #[test]
     fn test_solve_actual_from_example() {
         let problem = Problem15::new();
-        let input = get_input!("./inputs/problem_15_example.txt");
-        let risk_levels = problem.parse(input);
+        let input = get_input!("./inputs/problem_15_example.txt").unwrap();
+        let risk_levels = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual(&risk_levels), 40);
     }
 
     #[test]
     fn test_solve_actual_from_input() {
         let problem = Problem15::new();
-        let input = get_input!("./inputs/problem_15.txt");
-        let risk_levels = problem.parse(input);
+        let input = get_input!("./inputs/problem_15.txt").unwrap();
+        let risk_levels = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual(&risk_levels), 503);
     }
 
+    #[test]
+    fn test_solve_actual_from_non_square_grid() {
+        let problem = Problem15::new();
+        let risk_levels = problem.parse("116\n138").unwrap();
+        assert_eq!(problem.solve_actual(&risk_levels), 12);
+        match problem.parse("116\n13") {
+            Err(AocError::Parse { line, .. }) => assert_eq!(line, 2),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_solve_actual_part2_from_example() {
         let problem = Problem15::new();
-        let input = get_input!("./inputs/problem_15_example.txt");
-        let risk_levels = problem.parse(input);
+        let input = get_input!("./inputs/problem_15_example.txt").unwrap();
+        let risk_levels = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual_part2(&risk_levels), 315);
     }
 
     #[test]
     fn test_solve_actual_part2_from_input() {
         let problem = Problem15::new();
-        let input = get_input!("./inputs/problem_15.txt");
-        let risk_levels = problem.parse(input);
+        let input = get_input!("./inputs/problem_15.txt").unwrap();
+        let risk_levels = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual_part2(&risk_levels), 2853);
     }
 }
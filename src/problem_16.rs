@@ -1,4 +1,4 @@
-use crate::problem::Problem;
+use crate::{answer::Answer, error::AocError, problem::Problem};
 
 #[derive(Debug)]
 pub struct Packet {
@@ -9,6 +9,29 @@ pub struct Packet {
 }
 
 impl Packet {
+    /// Decodes the outermost packet of a transmission given one bit per
+    /// byte, ignoring the padding after it.
+    pub fn decode(bits: &[u8]) -> Result<Packet, AocError> {
+        BitReader { bits, position: 0 }.packet()
+    }
+
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    pub fn type_id(&self) -> u8 {
+        self.type_id
+    }
+
+    /// The value of a literal packet, or 0 for an operator.
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    pub fn subpackets(&self) -> &[Packet] {
+        &self.subpackets
+    }
+
     pub fn sum_versions(&self) -> i64 {
         self.version as i64
             + self
@@ -17,102 +40,136 @@ impl Packet {
                 .fold(0, |acc, p| acc + p.sum_versions())
     }
 
-    pub fn evaluate(&self) -> i64 {
+    /// The value of the expression this packet encodes. Decoding makes sure
+    /// every operator has the subpackets it needs.
+    pub fn evaluate(&self) -> u128 {
+        let mut values = self.subpackets.iter().map(|p| p.evaluate());
         match self.type_id {
-            0 => self.subpackets.iter().fold(0, |acc, p| acc + p.evaluate()),
-            1 => self.subpackets.iter().fold(1, |acc, p| acc * p.evaluate()),
-            2 => self.subpackets.iter().map(|p| p.evaluate()).min().unwrap(),
-            3 => self.subpackets.iter().map(|p| p.evaluate()).max().unwrap(),
-            4 => self.value as i64,
-            5 => match self.subpackets[0].evaluate() > self.subpackets[1].evaluate() {
-                true => 1,
-                false => 0,
-            },
-            6 => match self.subpackets[0].evaluate() < self.subpackets[1].evaluate() {
-                true => 1,
-                false => 0,
-            },
-            _ => match self.subpackets[0].evaluate() == self.subpackets[1].evaluate() {
-                true => 1,
-                false => 0,
-            },
+            0 => values.sum(),
+            1 => values.product(),
+            2 => values.min().expect("minimum packets have subpackets"),
+            3 => values.max().expect("maximum packets have subpackets"),
+            4 => self.value as u128,
+            _ => {
+                let (Some(first), Some(second)) = (values.next(), values.next()) else {
+                    unreachable!("comparison packets have two subpackets");
+                };
+                let holds = match self.type_id {
+                    5 => first > second,
+                    6 => first < second,
+                    _ => first == second,
+                };
+                holds as u128
+            }
         }
     }
 }
 
-fn parse_packet(packet: &Vec<u8>, packet_limit: usize, bit_limit: usize) -> (Vec<Packet>, usize) {
-    let mut index = 0;
-    let mut packet_stack = Vec::new();
-    while index < packet.len() - 6 {
-        if bit_limit > 0 && index >= bit_limit {
-            break;
-        }
-        if packet_limit > 0 && packet_stack.len() == packet_limit {
-            break;
-        }
+/// Reads a transmission's bits, and reports where it went wrong if it runs
+/// out or holds something that isn't a packet.
+struct BitReader<'a> {
+    bits: &'a [u8],
+    position: usize,
+}
+
+impl BitReader<'_> {
+    /// An error about the bit at `position`, on the hex digit holding it.
+    fn error(&self, position: usize, message: &str) -> AocError {
+        AocError::parse(
+            1,
+            position / 4 + 1,
+            format!("{} at bit {}", message, position),
+        )
+    }
 
-        let version = (packet[index] << 2) + (packet[index + 1] << 1) + packet[index + 2];
-        let type_id = (packet[index + 3] << 2) + (packet[index + 4] << 1) + packet[index + 5];
+    fn read(&mut self, count: usize, what: &str) -> Result<u64, AocError> {
+        let bits = self
+            .bits
+            .get(self.position..self.position + count)
+            .ok_or_else(|| {
+                self.error(
+                    self.position,
+                    &format!("transmission ends before the {}", what),
+                )
+            })?;
+        self.position += count;
+        Ok(bits.iter().fold(0, |acc, bit| (acc << 1) | *bit as u64))
+    }
+
+    fn packet(&mut self) -> Result<Packet, AocError> {
+        let start = self.position;
+        let version = self.read(3, "packet version")? as u8;
+        let type_id = self.read(3, "packet type")? as u8;
 
-        if type_id == 0x4 {
-            index += 6;
-            let mut value = 0;
+        if type_id == 4 {
+            let mut value: u64 = 0;
             loop {
-                let first_bit = packet[index];
-                value = (value << 4)
-                    + ((packet[index + 1] << 3) as u64
-                        + (packet[index + 2] << 2) as u64
-                        + (packet[index + 3] << 1) as u64
-                        + packet[index + 4] as u64);
-                index += 5;
-                if first_bit == 0 {
+                let group = self.read(5, "literal value")?;
+                if value > u64::MAX >> 4 {
+                    return Err(self.error(start, "literal value is too large"));
+                }
+                value = (value << 4) | (group & 0xf);
+                if group & 0x10 == 0 {
                     break;
                 }
             }
-            packet_stack.push(Packet {
+            return Ok(Packet {
                 version,
                 type_id,
                 value,
-                subpackets: Vec::with_capacity(0),
+                subpackets: Vec::new(),
             });
-        } else {
-            let length_type = packet[index + 6];
-            let offset = match length_type {
-                0x0 => 15,
-                _ => 11,
-            };
-            index += 7;
-            if index >= packet.len() - (offset + 1) {
-                continue;
+        }
+
+        let mut subpackets = Vec::new();
+        match self.read(1, "length type")? {
+            0 => {
+                let length = self.read(15, "subpacket length")? as usize;
+                let end = self.position + length;
+                if end > self.bits.len() {
+                    return Err(self.error(self.position, "subpackets run past the transmission"));
+                }
+                while self.position < end {
+                    subpackets.push(self.packet()?);
+                }
+                if self.position > end {
+                    return Err(self.error(end, "subpackets run past their length"));
+                }
             }
-            let length = packet[index..index + offset]
-                .iter()
-                .fold(0usize, |acc, v| (acc << 1) + (*v as usize & 0x1));
-
-            let subpackets = parse_packet(
-                &packet[index + offset..].to_vec(),
-                match length_type == 0x1 {
-                    true => length,
-                    _ => 0,
-                },
-                match length_type == 0x0 {
-                    true => length,
-                    _ => 0,
-                },
-            );
-
-            packet_stack.push(Packet {
-                version,
-                type_id,
-                value: 0,
-                subpackets: subpackets.0,
-            });
-            index += offset + subpackets.1;
+            _ => {
+                let count = self.read(11, "subpacket count")?;
+                for _ in 0..count {
+                    subpackets.push(self.packet()?);
+                }
+            }
+        }
+
+        let expected = match type_id {
+            0..=3 if subpackets.is_empty() => Some("at least one subpacket"),
+            5..=7 if subpackets.len() != 2 => Some("exactly two subpackets"),
+            _ => None,
+        };
+        if let Some(expected) = expected {
+            return Err(self.error(
+                start,
+                &format!(
+                    "a type {} packet needs {}, found {}",
+                    type_id,
+                    expected,
+                    subpackets.len()
+                ),
+            ));
         }
+        Ok(Packet {
+            version,
+            type_id,
+            value: 0,
+            subpackets,
+        })
     }
-    (packet_stack, index)
 }
 
+#[derive(Default)]
 pub struct Problem16 {}
 
 impl Problem16 {
@@ -120,48 +177,55 @@ impl Problem16 {
         Problem16 {}
     }
 
-    fn parse(&self, input: String) -> Vec<u8> {
-        input
-            .chars()
-            .map(|c| {
-                let parsed = c.to_digit(16).unwrap() as u8;
-                vec![
-                    (parsed & 0x8) >> 3,
-                    (parsed & 0x4) >> 2,
-                    (parsed & 0x2) >> 1,
-                    parsed & 0x1,
-                ]
-            })
-            .flatten()
-            .collect::<Vec<u8>>()
+    pub fn solve_actual(&self, packet: &Packet) -> i64 {
+        packet.sum_versions()
     }
 
-    fn solve_actual(&self, packet: &Vec<u8>) -> i64 {
-        let parsed_packet = &parse_packet(packet, 0, 0).0[0];
-        parsed_packet.sum_versions()
-    }
-
-    fn solve_actual_part2(&self, packet: &Vec<u8>) -> i64 {
-        let parsed_packet = &parse_packet(packet, 0, 0).0[0];
-        parsed_packet.evaluate()
+    pub fn solve_actual_part2(&self, packet: &Packet) -> u128 {
+        packet.evaluate()
     }
 }
 
 impl Problem for Problem16 {
+    type Parsed = Packet;
+
     fn name(&self) -> &str {
-        "Day 16: Packet Decoder"
+        Self::NAME
+    }
+
+    fn day(&self) -> u8 {
+        Self::DAY
+    }
+
+    fn default_input(&self) -> Result<String, AocError> {
+        get_input!("./inputs/problem_16.txt")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, AocError> {
+        if input.is_empty() {
+            return Err(AocError::parse(1, 1, "expected a hexadecimal transmission"));
+        }
+        let mut bits = Vec::with_capacity(input.len() * 4);
+        for (column, c) in input.chars().enumerate() {
+            let parsed = c.to_digit(16).ok_or_else(|| {
+                AocError::parse(1, column + 1, format!("'{}' is not a hexadecimal digit", c))
+            })? as u8;
+            bits.extend([
+                (parsed & 0x8) >> 3,
+                (parsed & 0x4) >> 2,
+                (parsed & 0x2) >> 1,
+                parsed & 0x1,
+            ]);
+        }
+        Packet::decode(&bits)
     }
 
-    fn solve(&self) -> i64 {
-        let input = get_input!("./inputs/problem_16.txt");
-        let packet = self.parse(input);
-        self.solve_actual(&packet)
+    fn part1(&self, packet: &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual(packet).into())
     }
 
-    fn solve_part2(&self) -> (i64, Option<String>) {
-        let input = get_input!("./inputs/problem_16.txt");
-        let packet = self.parse(input);
-        (self.solve_actual_part2(&packet), None)
+    fn part2(&self, packet: &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual_part2(packet).into())
     }
 }
 
@@ -172,96 +236,155 @@ mod tests {
     #[test]
     fn test_solve_actual_from_example_01() {
         let problem = Problem16::new();
-        let input = get_input!("./inputs/problem_16_example_01.txt");
-        let packet = problem.parse(input);
+        let input = get_input!("./inputs/problem_16_example_01.txt").unwrap();
+        let packet = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual(&packet), 6);
     }
 
     #[test]
     fn test_solve_actual_from_example_02() {
         let problem = Problem16::new();
-        let input = get_input!("./inputs/problem_16_example_02.txt");
-        let packet = problem.parse(input);
+        let input = get_input!("./inputs/problem_16_example_02.txt").unwrap();
+        let packet = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual(&packet), 9);
     }
 
     #[test]
     fn test_solve_actual_from_example_03() {
         let problem = Problem16::new();
-        let input = get_input!("./inputs/problem_16_example_03.txt");
-        let packet = problem.parse(input);
+        let input = get_input!("./inputs/problem_16_example_03.txt").unwrap();
+        let packet = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual(&packet), 14);
     }
 
     #[test]
     fn test_solve_actual_from_example_04() {
         let problem = Problem16::new();
-        let input = get_input!("./inputs/problem_16_example_04.txt");
-        let packet = problem.parse(input);
+        let input = get_input!("./inputs/problem_16_example_04.txt").unwrap();
+        let packet = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual(&packet), 16);
     }
 
     #[test]
     fn test_solve_actual_from_example_05() {
         let problem = Problem16::new();
-        let input = get_input!("./inputs/problem_16_example_05.txt");
-        let packet = problem.parse(input);
+        let input = get_input!("./inputs/problem_16_example_05.txt").unwrap();
+        let packet = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual(&packet), 12);
     }
 
     #[test]
     fn test_solve_actual_from_example_06() {
         let problem = Problem16::new();
-        let input = get_input!("./inputs/problem_16_example_06.txt");
-        let packet = problem.parse(input);
+        let input = get_input!("./inputs/problem_16_example_06.txt").unwrap();
+        let packet = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual(&packet), 23);
     }
 
     #[test]
     fn test_solve_actual_from_example_07() {
         let problem = Problem16::new();
-        let input = get_input!("./inputs/problem_16_example_07.txt");
-        let packet = problem.parse(input);
+        let input = get_input!("./inputs/problem_16_example_07.txt").unwrap();
+        let packet = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual(&packet), 31);
     }
 
     #[test]
     fn test_solve_actual_from_input() {
         let problem = Problem16::new();
-        let input = get_input!("./inputs/problem_16.txt");
-        let packet = problem.parse(input);
+        let input = get_input!("./inputs/problem_16.txt").unwrap();
+        let packet = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual(&packet), 879);
     }
 
+    #[test]
+    fn test_parse_rejects_non_hex() {
+        let problem = Problem16::new();
+        match problem.parse("8A004G4A801A8002F478") {
+            Err(AocError::Parse { line, column, .. }) => assert_eq!((line, column), (1, 6)),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+        assert!(problem.parse("").is_err());
+    }
+
+    #[test]
+    fn test_decode() {
+        // 110 100 10111 11110 00101 000: the literal 2021, version 6.
+        let bits: Vec<u8> = "110100101111111000101000"
+            .bytes()
+            .map(|bit| bit - b'0')
+            .collect();
+        let packet = Packet::decode(&bits).unwrap();
+        assert_eq!(
+            (packet.version(), packet.type_id(), packet.value()),
+            (6, 4, 2021)
+        );
+        assert!(packet.subpackets().is_empty());
+        assert!(Packet::decode(&bits[..20]).is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_truncated_transmissions() {
+        let problem = Problem16::new();
+        assert!(problem.parse("00").is_err());
+        // A literal whose value stops after its first group's marker bit.
+        match problem.parse("D2FE") {
+            Err(AocError::Parse {
+                line,
+                column,
+                message,
+            }) => {
+                assert_eq!((line, column), (1, 5));
+                assert!(message.ends_with("at bit 16"), "{}", message);
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_rejects_operators_missing_subpackets() {
+        let problem = Problem16::new();
+        // A minimum packet whose subpackets take up zero bits.
+        assert!(problem.parse("080000").is_err());
+        // A greater-than packet with only one subpacket, the literal 1.
+        match problem.parse("16004408") {
+            Err(AocError::Parse { column, message, .. }) => {
+                assert_eq!(column, 1);
+                assert!(message.contains("exactly two subpackets"), "{}", message);
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_solve_actual_part2_from_example_08() {
         let problem = Problem16::new();
-        let input = get_input!("./inputs/problem_16_example_08.txt");
-        let packet = problem.parse(input);
+        let input = get_input!("./inputs/problem_16_example_08.txt").unwrap();
+        let packet = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual_part2(&packet), 3);
     }
 
     #[test]
     fn test_solve_actual_part2_from_example_09() {
         let problem = Problem16::new();
-        let input = get_input!("./inputs/problem_16_example_09.txt");
-        let packet = problem.parse(input);
+        let input = get_input!("./inputs/problem_16_example_09.txt").unwrap();
+        let packet = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual_part2(&packet), 9);
     }
 
     #[test]
     fn test_solve_actual_part2_from_example_10() {
         let problem = Problem16::new();
-        let input = get_input!("./inputs/problem_16_example_10.txt");
-        let packet = problem.parse(input);
+        let input = get_input!("./inputs/problem_16_example_10.txt").unwrap();
+        let packet = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual_part2(&packet), 1);
     }
 
     #[test]
     fn test_solve_actual_part2_from_input() {
         let problem = Problem16::new();
-        let input = get_input!("./inputs/problem_16.txt");
-        let packet = problem.parse(input);
+        let input = get_input!("./inputs/problem_16.txt").unwrap();
+        let packet = problem.parse(&input).unwrap();
         assert_eq!(problem.solve_actual_part2(&packet), 539051801941);
     }
 }
@@ -0,0 +1,207 @@
+use crate::{
+    answer::Answer,
+    error::AocError,
+    problem::Problem,
+    util::{column_of, parse_token},
+};
+
+pub struct TargetArea {
+    x_min: i64,
+    x_max: i64,
+    y_min: i64,
+    y_max: i64,
+}
+
+impl TargetArea {
+    fn contains(&self, x: i64, y: i64) -> bool {
+        (self.x_min..=self.x_max).contains(&x) && (self.y_min..=self.y_max).contains(&y)
+    }
+
+    /// Fires the probe and returns the highest point it reaches, if it ever
+    /// ends a step inside the target area.
+    fn launch(&self, mut x_velocity: i64, mut y_velocity: i64) -> Option<i64> {
+        let (mut x, mut y, mut apex) = (0, 0, 0);
+        // Once the probe is past the far edge or below the bottom, it can
+        // only get further away.
+        while x <= self.x_max && y >= self.y_min {
+            x += x_velocity;
+            y += y_velocity;
+            apex = apex.max(y);
+            if self.contains(x, y) {
+                return Some(apex);
+            }
+            x_velocity -= x_velocity.signum();
+            y_velocity -= 1;
+        }
+        None
+    }
+
+    /// Apexes of every initial velocity that hits the target. Any faster to
+    /// the right overshoots in the first step, and because the probe comes
+    /// back down through y=0 at the speed it went up, anything faster
+    /// upwards overshoots the bottom right after.
+    fn hits(&self) -> Vec<i64> {
+        (0..=self.x_max)
+            .flat_map(|x_velocity| {
+                (self.y_min..=-self.y_min)
+                    .filter_map(move |y_velocity| self.launch(x_velocity, y_velocity))
+            })
+            .collect()
+    }
+}
+
+#[derive(Default)]
+pub struct Problem17 {}
+
+impl Problem17 {
+    pub fn new() -> Problem17 {
+        Problem17 {}
+    }
+
+    fn parse_range(&self, line: &str, range: &str) -> Result<(i64, i64), AocError> {
+        match range.split_once("..") {
+            Some((start, end)) => {
+                let (start, end) = (parse_token(0, line, start)?, parse_token(0, line, end)?);
+                match start <= end {
+                    true => Ok((start, end)),
+                    false => Err(AocError::parse(
+                        1,
+                        column_of(line, range),
+                        format!("range '{}' is backwards", range),
+                    )),
+                }
+            }
+            None => Err(AocError::parse(
+                1,
+                column_of(line, range),
+                format!("expected a range like 'a..b', found '{}'", range),
+            )),
+        }
+    }
+
+    pub fn solve_actual(&self, target: &TargetArea) -> i64 {
+        target.hits().into_iter().max().unwrap_or(0)
+    }
+
+    pub fn solve_actual_part2(&self, target: &TargetArea) -> i64 {
+        target.hits().len() as i64
+    }
+}
+
+impl Problem for Problem17 {
+    type Parsed = TargetArea;
+
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    fn day(&self) -> u8 {
+        Self::DAY
+    }
+
+    fn default_input(&self) -> Result<String, AocError> {
+        get_input!("./inputs/problem_17.txt")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, AocError> {
+        let line = input.trim_end();
+        let (x_range, y_range) = match line
+            .strip_prefix("target area: x=")
+            .and_then(|ranges| ranges.split_once(", y="))
+        {
+            Some(ranges) => ranges,
+            None => {
+                return Err(AocError::parse(
+                    1,
+                    1,
+                    "expected 'target area: x=a..b, y=c..d'",
+                ))
+            }
+        };
+        let (x_min, x_max) = self.parse_range(line, x_range)?;
+        let (y_min, y_max) = self.parse_range(line, y_range)?;
+        // The search for velocities relies on the probe having to go right
+        // and then fall to reach the target.
+        if x_min <= 0 || y_max >= 0 {
+            return Err(AocError::parse(
+                1,
+                1,
+                "expected the target area to be right of and below the launcher",
+            ));
+        }
+        Ok(TargetArea {
+            x_min,
+            x_max,
+            y_min,
+            y_max,
+        })
+    }
+
+    fn part1(&self, target: &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual(target).into())
+    }
+
+    fn part2(&self, target: &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual_part2(target).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_launch_from_example() {
+        let problem = Problem17::new();
+        let input = get_input!("./inputs/problem_17_example.txt").unwrap();
+        let target = problem.parse(&input).unwrap();
+        assert_eq!(target.launch(7, 2), Some(3));
+        assert_eq!(target.launch(6, 3), Some(6));
+        assert_eq!(target.launch(9, 0), Some(0));
+        assert_eq!(target.launch(17, -4), None);
+    }
+
+    #[test]
+    fn test_solve_actual_from_example() {
+        let problem = Problem17::new();
+        let input = get_input!("./inputs/problem_17_example.txt").unwrap();
+        let target = problem.parse(&input).unwrap();
+        assert_eq!(problem.solve_actual(&target), 45);
+    }
+
+    #[test]
+    fn test_solve_actual_from_input() {
+        let problem = Problem17::new();
+        let input = get_input!("./inputs/problem_17.txt").unwrap();
+        let target = problem.parse(&input).unwrap();
+        assert_eq!(problem.solve_actual(&target), 3655);
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_target_areas() {
+        let problem = Problem17::new();
+        match problem.parse("target area: x=20..30, y=-10..x") {
+            Err(AocError::Parse { line, column, .. }) => assert_eq!((line, column), (1, 31)),
+            other => panic!("expected a parse error, got {:?}", other.map(|_| ())),
+        }
+        assert!(problem.parse("target area: x=30..20, y=-10..-5").is_err());
+        assert!(problem.parse("target area: x=20..30, y=5..10").is_err());
+        assert!(problem.parse("x=20..30, y=-10..-5").is_err());
+    }
+
+    #[test]
+    fn test_solve_actual_part2_from_example() {
+        let problem = Problem17::new();
+        let input = get_input!("./inputs/problem_17_example.txt").unwrap();
+        let target = problem.parse(&input).unwrap();
+        assert_eq!(problem.solve_actual_part2(&target), 112);
+    }
+
+    #[test]
+    fn test_solve_actual_part2_from_input() {
+        let problem = Problem17::new();
+        let input = get_input!("./inputs/problem_17.txt").unwrap();
+        let target = problem.parse(&input).unwrap();
+        assert_eq!(problem.solve_actual_part2(&target), 1447);
+    }
+}
@@ -0,0 +1,446 @@
+use std::{fmt, ops::Add};
+
+use crate::{answer::Answer, error::AocError, problem::Problem};
+
+/// One regular number in a snailfish number, with how many pairs it is
+/// nested inside.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Element {
+    value: u32,
+    depth: u8,
+}
+
+/// A snailfish number, stored as its regular numbers from left to right.
+/// The pairs are implied by the depths: at any depth, elements form pairs
+/// from the left, so the tree can always be rebuilt.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnailfishNumber {
+    elements: Vec<Element>,
+}
+
+impl SnailfishNumber {
+    /// Parses a reduced number like `[[1,2],3]`, so one with no pair nested
+    /// inside four pairs. `line_index` is only used for error positions.
+    fn parse(line_index: usize, line: &str) -> Result<SnailfishNumber, AocError> {
+        Self::parse_nested(line_index, line, 3)
+    }
+
+    /// Like `parse`, but allows pairs nested inside up to `max_depth` pairs.
+    fn parse_nested(
+        line_index: usize,
+        line: &str,
+        max_depth: u8,
+    ) -> Result<SnailfishNumber, AocError> {
+        let mut elements = Vec::new();
+        let end = Self::parse_element(
+            line_index,
+            line.as_bytes(),
+            0,
+            0,
+            max_depth,
+            &mut elements,
+        )?;
+        if end != line.len() {
+            return Err(AocError::parse(
+                line_index + 1,
+                end + 1,
+                "unexpected characters after the number",
+            ));
+        }
+        if elements.len() < 2 {
+            return Err(AocError::parse(line_index + 1, 1, "expected a pair"));
+        }
+        Ok(SnailfishNumber { elements })
+    }
+
+    // Parses the element starting at `position`, and returns the position
+    // just after it.
+    fn parse_element(
+        line_index: usize,
+        line: &[u8],
+        position: usize,
+        depth: u8,
+        max_depth: u8,
+        elements: &mut Vec<Element>,
+    ) -> Result<usize, AocError> {
+        let expect = |position: usize, expected: u8| match line.get(position) {
+            Some(c) if *c == expected => Ok(position + 1),
+            found => Err(AocError::parse(
+                line_index + 1,
+                position + 1,
+                format!(
+                    "expected '{}', found {}",
+                    expected as char,
+                    found.map_or("the end of the line".to_string(), |c| format!(
+                        "'{}'",
+                        *c as char
+                    ))
+                ),
+            )),
+        };
+
+        match line.get(position) {
+            // Exploding relies on the deepest pairs holding two numbers,
+            // which only holds for a reduced number or a sum of two.
+            Some(b'[') if depth > max_depth => Err(AocError::parse(
+                line_index + 1,
+                position + 1,
+                format!("pairs can't be nested inside more than {} pairs", max_depth),
+            )),
+            Some(b'[') => {
+                let position = Self::parse_element(
+                    line_index,
+                    line,
+                    position + 1,
+                    depth + 1,
+                    max_depth,
+                    elements,
+                )?;
+                let position = expect(position, b',')?;
+                let position = Self::parse_element(
+                    line_index,
+                    line,
+                    position,
+                    depth + 1,
+                    max_depth,
+                    elements,
+                )?;
+                expect(position, b']')
+            }
+            Some(c) if c.is_ascii_digit() => {
+                let digits = line[position..]
+                    .iter()
+                    .take_while(|c| c.is_ascii_digit())
+                    .count();
+                let value = line[position..position + digits]
+                    .iter()
+                    .try_fold(0u32, |value, digit| {
+                        value.checked_mul(10)?.checked_add((digit - b'0') as u32)
+                    })
+                    .ok_or_else(|| {
+                        AocError::parse(line_index + 1, position + 1, "number is too large")
+                    })?;
+                elements.push(Element { value, depth });
+                Ok(position + digits)
+            }
+            _ => Err(AocError::parse(
+                line_index + 1,
+                position + 1,
+                "expected '[' or a number",
+            )),
+        }
+    }
+
+    /// Explodes the leftmost pair nested inside four pairs, if there is one.
+    fn explode(&mut self) -> bool {
+        let Some(index) = self.elements.iter().position(|element| element.depth > 4) else {
+            return false;
+        };
+        let (left, right) = (self.elements[index], self.elements[index + 1]);
+        if index > 0 {
+            self.elements[index - 1].value += left.value;
+        }
+        if let Some(next) = self.elements.get_mut(index + 2) {
+            next.value += right.value;
+        }
+        self.elements.splice(
+            index..index + 2,
+            [Element {
+                value: 0,
+                depth: left.depth - 1,
+            }],
+        );
+        true
+    }
+
+    /// Splits the leftmost regular number of 10 or more, if there is one.
+    fn split(&mut self) -> bool {
+        let Some(index) = self.elements.iter().position(|element| element.value >= 10) else {
+            return false;
+        };
+        let Element { value, depth } = self.elements[index];
+        self.elements.splice(
+            index..=index,
+            [
+                Element {
+                    value: value / 2,
+                    depth: depth + 1,
+                },
+                Element {
+                    value: value.div_ceil(2),
+                    depth: depth + 1,
+                },
+            ],
+        );
+        true
+    }
+
+    /// Explodes, and only when nothing explodes splits, until neither
+    /// applies.
+    fn reduce(&mut self) {
+        while self.explode() || self.split() {}
+    }
+
+    pub fn magnitude(&self) -> u64 {
+        let mut elements: Vec<(u64, u8)> = self
+            .elements
+            .iter()
+            .map(|element| (element.value as u64, element.depth))
+            .collect();
+        // The deepest elements always pair up with their right neighbour, so
+        // collapse those until only the outermost pair's magnitude is left.
+        while elements.len() > 1 {
+            let deepest = elements.iter().map(|(_, depth)| *depth).max().unwrap_or(0);
+            let index = elements
+                .iter()
+                .position(|(_, depth)| *depth == deepest)
+                .unwrap_or(0);
+            let (left, right) = (elements[index].0, elements[index + 1].0);
+            elements.splice(index..index + 2, [(3 * left + 2 * right, deepest - 1)]);
+        }
+        elements.first().map_or(0, |(magnitude, _)| *magnitude)
+    }
+
+    fn write_element(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        index: &mut usize,
+        depth: u8,
+    ) -> fmt::Result {
+        let element = self.elements[*index];
+        if element.depth == depth {
+            *index += 1;
+            return write!(f, "{}", element.value);
+        }
+        write!(f, "[")?;
+        self.write_element(f, index, depth + 1)?;
+        write!(f, ",")?;
+        self.write_element(f, index, depth + 1)?;
+        write!(f, "]")
+    }
+}
+
+impl Add for &SnailfishNumber {
+    type Output = SnailfishNumber;
+
+    fn add(self, other: &SnailfishNumber) -> SnailfishNumber {
+        let mut sum = SnailfishNumber {
+            elements: self
+                .elements
+                .iter()
+                .chain(&other.elements)
+                .map(|element| Element {
+                    value: element.value,
+                    depth: element.depth + 1,
+                })
+                .collect(),
+        };
+        sum.reduce();
+        sum
+    }
+}
+
+impl fmt::Display for SnailfishNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_element(f, &mut 0, 0)
+    }
+}
+
+#[derive(Default)]
+pub struct Problem18 {}
+
+impl Problem18 {
+    pub fn new() -> Problem18 {
+        Problem18 {}
+    }
+
+    pub fn solve_actual(&self, homework: &[SnailfishNumber]) -> i64 {
+        homework
+            .iter()
+            .skip(1)
+            .fold(homework[0].clone(), |sum, number| &sum + number)
+            .magnitude() as i64
+    }
+
+    pub fn solve_actual_part2(&self, homework: &[SnailfishNumber]) -> i64 {
+        let mut largest = 0;
+        for (i, first) in homework.iter().enumerate() {
+            for (j, second) in homework.iter().enumerate() {
+                if i != j {
+                    largest = largest.max((first + second).magnitude());
+                }
+            }
+        }
+        largest as i64
+    }
+}
+
+impl Problem for Problem18 {
+    type Parsed = Vec<SnailfishNumber>;
+
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    fn day(&self) -> u8 {
+        Self::DAY
+    }
+
+    fn default_input(&self) -> Result<String, AocError> {
+        get_input!("./inputs/problem_18.txt")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, AocError> {
+        let homework = input
+            .lines()
+            .enumerate()
+            .map(|(index, line)| SnailfishNumber::parse(index, line))
+            .collect::<Result<Vec<SnailfishNumber>, AocError>>()?;
+        match homework.is_empty() {
+            true => Err(AocError::parse(
+                1,
+                1,
+                "expected at least one snailfish number",
+            )),
+            false => Ok(homework),
+        }
+    }
+
+    fn part1(&self, homework: &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual(homework).into())
+    }
+
+    fn part2(&self, homework: &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual_part2(homework).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Parses a number that may be partway through being reduced.
+    fn number(line: &str) -> SnailfishNumber {
+        SnailfishNumber::parse_nested(0, line, 4).unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_display_round_trip() {
+        for line in [
+            "[1,2]",
+            "[[1,2],3]",
+            "[[[[1,3],[5,3]],[[1,3],[8,7]]],[[[4,9],[6,9]],[[8,2],[7,3]]]]",
+        ] {
+            assert_eq!(number(line).to_string(), line);
+        }
+    }
+
+    #[test]
+    fn test_explode() {
+        for (before, after) in [
+            ("[[[[[9,8],1],2],3],4]", "[[[[0,9],2],3],4]"),
+            ("[7,[6,[5,[4,[3,2]]]]]", "[7,[6,[5,[7,0]]]]"),
+            ("[[6,[5,[4,[3,2]]]],1]", "[[6,[5,[7,0]]],3]"),
+            (
+                "[[3,[2,[1,[7,3]]]],[6,[5,[4,[3,2]]]]]",
+                "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]",
+            ),
+            (
+                "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]",
+                "[[3,[2,[8,0]]],[9,[5,[7,0]]]]",
+            ),
+        ] {
+            let mut number = number(before);
+            assert!(number.explode());
+            assert_eq!(number.to_string(), after);
+        }
+        assert!(!number("[[[[0,9],2],3],4]").explode());
+    }
+
+    #[test]
+    fn test_split() {
+        let mut number = number("[[[[0,7],4],[15,[0,13]]],[1,1]]");
+        assert!(number.split());
+        assert_eq!(number.to_string(), "[[[[0,7],4],[[7,8],[0,13]]],[1,1]]");
+        assert!(number.split());
+        assert_eq!(number.to_string(), "[[[[0,7],4],[[7,8],[0,[6,7]]]],[1,1]]");
+        assert!(!number.split());
+    }
+
+    #[test]
+    fn test_add_reduces() {
+        assert_eq!(
+            (&number("[[[[4,3],4],4],[7,[[8,4],9]]]") + &number("[1,1]")).to_string(),
+            "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]"
+        );
+    }
+
+    #[test]
+    fn test_magnitude() {
+        assert_eq!(number("[[1,2],[[3,4],5]]").magnitude(), 143);
+        assert_eq!(
+            number("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]").magnitude(),
+            1384
+        );
+        assert_eq!(
+            number("[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]").magnitude(),
+            3488
+        );
+    }
+
+    #[test]
+    fn test_solve_actual_from_example() {
+        let problem = Problem18::new();
+        let input = get_input!("./inputs/problem_18_example.txt").unwrap();
+        let homework = problem.parse(&input).unwrap();
+        assert_eq!(problem.solve_actual(&homework), 4140);
+    }
+
+    #[test]
+    fn test_solve_actual_from_input() {
+        let problem = Problem18::new();
+        let input = get_input!("./inputs/problem_18.txt").unwrap();
+        let homework = problem.parse(&input).unwrap();
+        assert_eq!(problem.solve_actual(&homework), 4287);
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_numbers() {
+        let problem = Problem18::new();
+        match problem.parse("[1,2]\n[[1,2],3") {
+            Err(AocError::Parse { line, column, .. }) => assert_eq!((line, column), (2, 9)),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+        assert!(problem.parse("[1,2,3]").is_err());
+        assert!(problem.parse("[1;2]").is_err());
+        assert!(problem.parse("7").is_err());
+        assert!(problem.parse("[[[[[[1,2],3],4],5],6],7]").is_err());
+        assert!(problem.parse("").is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_unreduced_numbers() {
+        let problem = Problem18::new();
+        match problem.parse("[[[[1,[2,3]],4],5],6]\n[0,0]") {
+            Err(AocError::Parse { line, column, .. }) => assert_eq!((line, column), (1, 7)),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+        assert!(problem.parse("[[[[1,2],3],4],5]\n[0,0]").is_ok());
+    }
+
+    #[test]
+    fn test_solve_actual_part2_from_example() {
+        let problem = Problem18::new();
+        let input = get_input!("./inputs/problem_18_example.txt").unwrap();
+        let homework = problem.parse(&input).unwrap();
+        assert_eq!(problem.solve_actual_part2(&homework), 3993);
+    }
+
+    #[test]
+    fn test_solve_actual_part2_from_input() {
+        let problem = Problem18::new();
+        let input = get_input!("./inputs/problem_18.txt").unwrap();
+        let homework = problem.parse(&input).unwrap();
+        assert_eq!(problem.solve_actual_part2(&homework), 4693);
+    }
+}
@@ -0,0 +1,440 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::{
+    answer::Answer,
+    error::AocError,
+    problem::Problem,
+    util::{column_of, parse_token},
+};
+
+type Position = [i32; 3];
+
+/// Scanners are aligned once they agree on at least this many beacons.
+const MIN_OVERLAP: usize = 12;
+
+/// Squared distances between pairs of beacons, with every pair that is that
+/// far apart.
+type Fingerprint = HashMap<i64, Vec<(usize, usize)>>;
+
+/// One of the 24 directions a scanner can face: a permutation of the axes
+/// with a sign for each, keeping the coordinate system right-handed.
+#[derive(Debug, Clone, Copy)]
+struct Rotation {
+    axes: [usize; 3],
+    signs: [i32; 3],
+}
+
+impl Rotation {
+    fn all() -> Vec<Rotation> {
+        let permutations = [
+            ([0, 1, 2], 1),
+            ([1, 2, 0], 1),
+            ([2, 0, 1], 1),
+            ([0, 2, 1], -1),
+            ([2, 1, 0], -1),
+            ([1, 0, 2], -1),
+        ];
+        let mut rotations = Vec::with_capacity(24);
+        for (axes, parity) in permutations {
+            for signs in 0..8 {
+                let signs = [0, 1, 2].map(|bit| match signs & (1 << bit) {
+                    0 => 1,
+                    _ => -1,
+                });
+                // Flipping an odd number of axes mirrors, and so does an odd
+                // permutation, so exactly one of them has to happen.
+                if signs.iter().product::<i32>() == parity {
+                    rotations.push(Rotation { axes, signs });
+                }
+            }
+        }
+        rotations
+    }
+
+    fn apply(&self, position: &Position) -> Position {
+        [0, 1, 2].map(|axis| self.signs[axis] * position[self.axes[axis]])
+    }
+}
+
+pub struct Scanner {
+    beacons: Vec<Position>,
+}
+
+impl Scanner {
+    /// Squared distances between every pair of beacons this scanner sees,
+    /// with the pairs' indices. These don't depend on where the scanner is
+    /// or which way it faces, so two scanners that see the same 12 beacons
+    /// share at least 66 of them, counting repeated distances.
+    fn fingerprint(&self) -> Fingerprint {
+        let mut distances = Fingerprint::new();
+        for (first, a) in self.beacons.iter().enumerate() {
+            for (second, b) in self.beacons.iter().enumerate().skip(first + 1) {
+                let distance = (0..3)
+                    .map(|axis| (a[axis] - b[axis]) as i64)
+                    .map(|delta| delta * delta)
+                    .sum();
+                distances
+                    .entry(distance)
+                    .or_default()
+                    .push((first, second));
+            }
+        }
+        distances
+    }
+}
+
+/// Where every scanner is relative to the first one, and all the beacons
+/// they see between them, in the first scanner's coordinates.
+pub struct Map {
+    scanners: Vec<Position>,
+    beacons: HashSet<Position>,
+}
+
+#[derive(Default)]
+pub struct Problem19 {}
+
+impl Problem19 {
+    pub fn new() -> Problem19 {
+        Problem19 {}
+    }
+
+    fn parse_position(&self, index: usize, line: &str) -> Result<Position, AocError> {
+        let coordinates: Vec<&str> = line.split(',').collect();
+        match coordinates[..] {
+            [x, y, z] => Ok([
+                parse_token(index, line, x)?,
+                parse_token(index, line, y)?,
+                parse_token(index, line, z)?,
+            ]),
+            _ => Err(AocError::parse(
+                index + 1,
+                1,
+                format!("expected a position like 'x,y,z', found '{}'", line),
+            )),
+        }
+    }
+
+    /// Finds the rotation and offset that puts at least `MIN_OVERLAP` of
+    /// `beacons` on top of `known` ones, returning `beacons` moved into the
+    /// known coordinates along with the offset, which is where the scanner
+    /// that saw them is.
+    ///
+    /// Each distance the two fingerprints share names a pair of beacons on
+    /// both sides that may be the same two beacons. Only rotations that line
+    /// those pairs up are worth checking against the rest of the beacons.
+    fn locate(
+        &self,
+        rotations: &[Rotation],
+        (known, known_fingerprint): (&[Position], &Fingerprint),
+        (beacons, fingerprint): (&[Position], &Fingerprint),
+    ) -> Option<(Vec<Position>, Position)> {
+        let known_set: HashSet<&Position> = known.iter().collect();
+        let difference = |a: &Position, b: &Position| [0, 1, 2].map(|axis| a[axis] - b[axis]);
+        let candidates = fingerprint.iter().flat_map(|(distance, pairs)| {
+            let known_pairs = known_fingerprint.get(distance).map_or(&[][..], Vec::as_slice);
+            pairs
+                .iter()
+                .flat_map(move |pair| known_pairs.iter().map(move |known_pair| (pair, known_pair)))
+        });
+        for (&(first, second), &(known_first, known_second)) in candidates {
+            let known_delta = difference(&known[known_second], &known[known_first]);
+            for rotation in rotations {
+                for (from, to) in [(first, second), (second, first)] {
+                    let start = rotation.apply(&beacons[from]);
+                    if difference(&rotation.apply(&beacons[to]), &start) != known_delta {
+                        continue;
+                    }
+                    let offset = difference(&known[known_first], &start);
+                    let moved: Vec<Position> = beacons
+                        .iter()
+                        .map(|beacon| {
+                            let rotated = rotation.apply(beacon);
+                            [0, 1, 2].map(|axis| rotated[axis] + offset[axis])
+                        })
+                        .collect();
+                    let overlap = moved.iter().filter(|b| known_set.contains(b)).count();
+                    if overlap >= MIN_OVERLAP {
+                        return Some((moved, offset));
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Aligns every scanner with the first, working outwards from scanners
+    /// that are already placed. Only pairs whose fingerprints share enough
+    /// distances, counting each as often as both sides have it, are tried.
+    fn map(&self, scanners: &[Scanner]) -> Result<Map, AocError> {
+        let rotations = Rotation::all();
+        let shared_distances = MIN_OVERLAP * (MIN_OVERLAP - 1) / 2;
+        let fingerprints: Vec<Fingerprint> = scanners.iter().map(Scanner::fingerprint).collect();
+        let shared = |a: &Fingerprint, b: &Fingerprint| -> usize {
+            a.iter()
+                .map(|(distance, pairs)| pairs.len().min(b.get(distance).map_or(0, Vec::len)))
+                .sum()
+        };
+
+        let mut placed: Vec<Option<(Vec<Position>, Position)>> =
+            scanners.iter().map(|_| None).collect();
+        placed[0] = Some((scanners[0].beacons.clone(), [0, 0, 0]));
+        let mut queue = VecDeque::from([0]);
+        while let Some(known) = queue.pop_front() {
+            for other in 0..scanners.len() {
+                if placed[other].is_some()
+                    || shared(&fingerprints[other], &fingerprints[known]) < shared_distances
+                {
+                    continue;
+                }
+                let known_beacons = &placed[known].as_ref().unwrap().0;
+                if let Some(location) = self.locate(
+                    &rotations,
+                    (known_beacons, &fingerprints[known]),
+                    (&scanners[other].beacons, &fingerprints[other]),
+                ) {
+                    placed[other] = Some(location);
+                    queue.push_back(other);
+                }
+            }
+        }
+
+        let mut map = Map {
+            scanners: Vec::with_capacity(scanners.len()),
+            beacons: HashSet::new(),
+        };
+        for (index, location) in placed.into_iter().enumerate() {
+            let (beacons, scanner) = location.ok_or_else(|| {
+                AocError::no_solution(format!(
+                    "scanner {} doesn't share {} beacons with any placed scanner",
+                    index, MIN_OVERLAP
+                ))
+            })?;
+            map.scanners.push(scanner);
+            map.beacons.extend(beacons);
+        }
+        Ok(map)
+    }
+
+    /// Reads each scanner's report of the beacons it sees.
+    fn parse_scanners(&self, input: &str) -> Result<Vec<Scanner>, AocError> {
+        let mut scanners: Vec<Scanner> = Vec::new();
+        let mut current: Option<Scanner> = None;
+        for (index, line) in input.lines().enumerate() {
+            if line.trim().is_empty() {
+                scanners.extend(current.take());
+                continue;
+            }
+            match (
+                &mut current,
+                line.strip_prefix("--- scanner ")
+                    .and_then(|rest| rest.strip_suffix(" ---")),
+            ) {
+                (None, Some(token)) => {
+                    let number: usize = parse_token(index, line, token)?;
+                    if number != scanners.len() {
+                        return Err(AocError::parse(
+                            index + 1,
+                            column_of(line, token),
+                            format!(
+                                "expected scanner {}, found scanner {}",
+                                scanners.len(),
+                                number
+                            ),
+                        ));
+                    }
+                    current = Some(Scanner {
+                        beacons: Vec::new(),
+                    });
+                }
+                (Some(scanner), None) => scanner.beacons.push(self.parse_position(index, line)?),
+                (None, None) => {
+                    return Err(AocError::parse(
+                        index + 1,
+                        1,
+                        "expected a header like '--- scanner 0 ---'",
+                    ))
+                }
+                (Some(_), Some(_)) => {
+                    return Err(AocError::parse(
+                        index + 1,
+                        1,
+                        "expected a blank line before the next scanner",
+                    ))
+                }
+            }
+        }
+        scanners.extend(current);
+        match scanners.is_empty() {
+            true => Err(AocError::parse(1, 1, "expected at least one scanner")),
+            false => Ok(scanners),
+        }
+    }
+
+    pub fn solve_actual(&self, map: &Map) -> i64 {
+        map.beacons.len() as i64
+    }
+
+    pub fn solve_actual_part2(&self, map: &Map) -> i64 {
+        let mut largest = 0;
+        for first in &map.scanners {
+            for second in &map.scanners {
+                let distance: i32 = (0..3).map(|axis| (first[axis] - second[axis]).abs()).sum();
+                largest = largest.max(distance);
+            }
+        }
+        largest as i64
+    }
+}
+
+impl Problem for Problem19 {
+    type Parsed = Map;
+
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    fn day(&self) -> u8 {
+        Self::DAY
+    }
+
+    fn default_input(&self) -> Result<String, AocError> {
+        get_input!("./inputs/problem_19.txt")
+    }
+
+    /// Aligns the scanners here, once, since both parts need the whole map.
+    fn parse(&self, input: &str) -> Result<Self::Parsed, AocError> {
+        let scanners = self.parse_scanners(input)?;
+        self.map(&scanners)
+    }
+
+    fn part1(&self, map: &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual(map).into())
+    }
+
+    fn part2(&self, map: &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual_part2(map).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotations_are_distinct_and_right_handed() {
+        let rotations = Rotation::all();
+        let images: HashSet<Position> = rotations.iter().map(|r| r.apply(&[1, 2, 3])).collect();
+        assert_eq!(images.len(), 24);
+        for rotation in rotations {
+            // The cross product of the rotated x and y axes is the rotated z.
+            let [x, y, z] = [[1, 0, 0], [0, 1, 0], [0, 0, 1]].map(|axis| rotation.apply(&axis));
+            let cross = [
+                x[1] * y[2] - x[2] * y[1],
+                x[2] * y[0] - x[0] * y[2],
+                x[0] * y[1] - x[1] * y[0],
+            ];
+            assert_eq!(cross, z);
+        }
+    }
+
+    #[test]
+    fn test_solve_actual_from_example() {
+        let problem = Problem19::new();
+        let input = get_input!("./inputs/problem_19_example.txt").unwrap();
+        let map = problem.parse(&input).unwrap();
+        assert_eq!(problem.solve_actual(&map), 79);
+    }
+
+    #[test]
+    fn test_map_places_example_scanners() {
+        let problem = Problem19::new();
+        let input = get_input!("./inputs/problem_19_example.txt").unwrap();
+        assert_eq!(
+            problem.parse(&input).unwrap().scanners,
+            vec![
+                [0, 0, 0],
+                [68, -1246, -43],
+                [1105, -1205, 1229],
+                [-92, -2380, -20],
+                [-20, -1133, 1061],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_map_counts_repeated_distances() {
+        // A 3 by 4 grid of beacons has only 11 different distances between
+        // its 66 pairs, but two scanners seeing all of it still overlap.
+        let grid: Vec<Position> = (0..3)
+            .flat_map(|x| (0..4).map(move |y| [x * 10, y * 10 + x, 0]))
+            .collect();
+        let lines = |offset: Position| -> String {
+            grid.iter()
+                .map(|[x, y, z]| {
+                    // Seen turned a quarter about z, from somewhere else.
+                    format!("{},{},{}", y + offset[0], -x + offset[1], z + offset[2])
+                })
+                .collect::<Vec<String>>()
+                .join("\n")
+        };
+        let input = format!(
+            "--- scanner 0 ---\n{}\n\n--- scanner 1 ---\n{}",
+            grid.iter()
+                .map(|[x, y, z]| format!("{},{},{}", x, y, z))
+                .collect::<Vec<String>>()
+                .join("\n"),
+            lines([100, 200, 300])
+        );
+        let problem = Problem19::new();
+        let map = problem.parse(&input).unwrap();
+        assert_eq!(problem.solve_actual(&map), 12);
+    }
+
+    #[test]
+    fn test_solve_actual_from_input() {
+        let problem = Problem19::new();
+        let input = get_input!("./inputs/problem_19.txt").unwrap();
+        let map = problem.parse(&input).unwrap();
+        assert_eq!(problem.solve_actual(&map), 532);
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_reports() {
+        let problem = Problem19::new();
+        match problem.parse("--- scanner 0 ---\n1,2,3\n4,x,6") {
+            Err(AocError::Parse { line, column, .. }) => assert_eq!((line, column), (3, 3)),
+            other => panic!("expected a parse error, got {:?}", other.map(|map| map.scanners)),
+        }
+        assert!(problem.parse("1,2,3").is_err());
+        assert!(problem.parse("--- scanner 0 ---\n1,2").is_err());
+        assert!(problem.parse("--- scanner 1 ---\n1,2,3").is_err());
+        assert!(problem
+            .parse("--- scanner 0 ---\n--- scanner 1 ---")
+            .is_err());
+    }
+
+    #[test]
+    fn test_disconnected_scanners_have_no_solution() {
+        let problem = Problem19::new();
+        assert!(matches!(
+            problem.parse("--- scanner 0 ---\n1,2,3\n\n--- scanner 1 ---\n4,5,6"),
+            Err(AocError::NoSolution { .. })
+        ));
+    }
+
+    #[test]
+    fn test_solve_actual_part2_from_example() {
+        let problem = Problem19::new();
+        let input = get_input!("./inputs/problem_19_example.txt").unwrap();
+        let map = problem.parse(&input).unwrap();
+        assert_eq!(problem.solve_actual_part2(&map), 3621);
+    }
+
+    #[test]
+    fn test_solve_actual_part2_from_input() {
+        let problem = Problem19::new();
+        let input = get_input!("./inputs/problem_19.txt").unwrap();
+        let map = problem.parse(&input).unwrap();
+        assert_eq!(problem.solve_actual_part2(&map), 17719);
+    }
+}
@@ -0,0 +1,210 @@
+use crate::{answer::Answer, error::AocError, grid::Grid, problem::Problem, util::parse_pixels};
+
+/// A finite window onto an infinite image. Every pixel outside the window
+/// has the same value, `background`.
+#[derive(Clone)]
+pub struct Image {
+    pixels: Grid<bool>,
+    background: bool,
+}
+
+impl Image {
+    fn pixel(&self, row: i64, col: i64) -> bool {
+        if row < 0 || col < 0 {
+            return self.background;
+        }
+        self.pixels
+            .get((row as usize, col as usize))
+            .copied()
+            .unwrap_or(self.background)
+    }
+
+    /// Applies the algorithm once. Only pixels next to the window can differ
+    /// from the background, so the window grows by one on every side. The
+    /// background itself becomes whatever the algorithm makes of nine
+    /// background pixels, which flips it every step when `algorithm[0]` is
+    /// lit and `algorithm[511]` isn't.
+    fn enhance(&self, algorithm: &[bool]) -> Image {
+        let mut pixels = Grid::new(self.pixels.width() + 2, self.pixels.height() + 2, false);
+        for (row, col) in pixels.positions() {
+            let (row, col) = (row as i64 - 1, col as i64 - 1);
+            let mut index = 0;
+            for neighbour_row in row - 1..=row + 1 {
+                for neighbour_col in col - 1..=col + 1 {
+                    index = (index << 1) | self.pixel(neighbour_row, neighbour_col) as usize;
+                }
+            }
+            pixels[((row + 1) as usize, (col + 1) as usize)] = algorithm[index];
+        }
+        let background = match self.background {
+            true => algorithm[511],
+            false => algorithm[0],
+        };
+        Image { pixels, background }
+    }
+
+    /// The number of lit pixels, or `None` when infinitely many are lit.
+    fn lit(&self) -> Option<usize> {
+        match self.background {
+            true => None,
+            false => Some(self.pixels.iter().filter(|(_, lit)| **lit).count()),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Problem20 {}
+
+impl Problem20 {
+    pub fn new() -> Problem20 {
+        Problem20 {}
+    }
+
+    pub fn solve_actual(
+        &self,
+        algorithm: &[bool],
+        image: &Image,
+        steps: usize,
+    ) -> Result<i64, AocError> {
+        let mut image = image.clone();
+        for _step in 0..steps {
+            image = image.enhance(algorithm);
+        }
+        match image.lit() {
+            Some(lit) => Ok(lit as i64),
+            None => Err(AocError::no_solution(format!(
+                "infinitely many pixels are lit after {} steps",
+                steps
+            ))),
+        }
+    }
+}
+
+impl Problem for Problem20 {
+    type Parsed = (Vec<bool>, Image);
+
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    fn day(&self) -> u8 {
+        Self::DAY
+    }
+
+    fn default_input(&self) -> Result<String, AocError> {
+        get_input!("./inputs/problem_20.txt")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, AocError> {
+        let mut lines = input.lines().enumerate();
+        let algorithm = match lines.next() {
+            Some((index, line)) => parse_pixels(index, line)?,
+            None => Vec::new(),
+        };
+        if algorithm.len() != 512 {
+            return Err(AocError::parse(
+                1,
+                1,
+                format!(
+                    "expected an algorithm of 512 pixels, found {}",
+                    algorithm.len()
+                ),
+            ));
+        }
+        match lines.next() {
+            Some((_, "")) => {}
+            _ => return Err(AocError::parse(2, 1, "expected a blank line")),
+        }
+
+        let mut lines = lines.peekable();
+        if lines.peek().is_none() {
+            return Err(AocError::parse(3, 1, "expected an image"));
+        }
+        let pixels = Grid::parse_lines(lines, "'#' or '.'", |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })?;
+        Ok((
+            algorithm,
+            Image {
+                pixels,
+                background: false,
+            },
+        ))
+    }
+
+    fn part1(&self, (algorithm, image): &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual(algorithm, image, 2)?.into())
+    }
+
+    fn part2(&self, (algorithm, image): &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual(algorithm, image, 50)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_actual_from_example() {
+        let problem = Problem20::new();
+        let input = get_input!("./inputs/problem_20_example.txt").unwrap();
+        let (algorithm, image) = problem.parse(&input).unwrap();
+        assert_eq!(problem.solve_actual(&algorithm, &image, 2).unwrap(), 35);
+    }
+
+    #[test]
+    fn test_solve_actual_from_input() {
+        let problem = Problem20::new();
+        let input = get_input!("./inputs/problem_20.txt").unwrap();
+        let (algorithm, image) = problem.parse(&input).unwrap();
+        assert_eq!(problem.solve_actual(&algorithm, &image, 2).unwrap(), 5465);
+    }
+
+    #[test]
+    fn test_background_flips_when_algorithm_lights_darkness() {
+        let problem = Problem20::new();
+        let input = get_input!("./inputs/problem_20.txt").unwrap();
+        let (algorithm, image) = problem.parse(&input).unwrap();
+        assert!(algorithm[0] && !algorithm[511]);
+        let once = image.enhance(&algorithm);
+        assert!(once.background);
+        assert_eq!(once.lit(), None);
+        assert!(!once.enhance(&algorithm).background);
+        assert!(matches!(
+            problem.solve_actual(&algorithm, &image, 1),
+            Err(AocError::NoSolution { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_input() {
+        let problem = Problem20::new();
+        let algorithm = "#".repeat(512);
+        match problem.parse(&format!("{}\n\n#.#\n#x#", algorithm)) {
+            Err(AocError::Parse { line, column, .. }) => assert_eq!((line, column), (4, 2)),
+            other => panic!("expected a parse error, got {:?}", other.map(|_| ())),
+        }
+        assert!(problem.parse(&format!("{}\n\n#.#\n##", algorithm)).is_err());
+        assert!(problem.parse(&format!("{}\n#.#", algorithm)).is_err());
+        assert!(problem.parse("#.#\n\n#.#").is_err());
+    }
+
+    #[test]
+    fn test_solve_actual_part2_from_example() {
+        let problem = Problem20::new();
+        let input = get_input!("./inputs/problem_20_example.txt").unwrap();
+        let (algorithm, image) = problem.parse(&input).unwrap();
+        assert_eq!(problem.solve_actual(&algorithm, &image, 50).unwrap(), 3351);
+    }
+
+    #[test]
+    fn test_solve_actual_part2_from_input() {
+        let problem = Problem20::new();
+        let input = get_input!("./inputs/problem_20.txt").unwrap();
+        let (algorithm, image) = problem.parse(&input).unwrap();
+        assert_eq!(problem.solve_actual(&algorithm, &image, 50).unwrap(), 19261);
+    }
+}
@@ -0,0 +1,209 @@
+use crate::{answer::Answer, error::AocError, problem::Problem, util::parse_token};
+
+/// How many of the 27 universes split off by three rolls of the Dirac die
+/// move a pawn by each total, from 3 to 9.
+const QUANTUM_ROLLS: [(u64, u64); 7] = [(3, 1), (4, 3), (5, 6), (6, 7), (7, 6), (8, 3), (9, 1)];
+
+const QUANTUM_TARGET: u8 = 21;
+
+fn advance(position: u8, roll: u64) -> u8 {
+    ((position as u64 + roll - 1) % 10 + 1) as u8
+}
+
+/// Universe counts for the player about to move and for their opponent,
+/// memoized by both pawns and scores. Pawns are 1-10 and scores stay below
+/// 21, so every state fits in a small table.
+struct QuantumGame {
+    wins: Vec<Option<(u64, u64)>>,
+}
+
+impl QuantumGame {
+    fn new() -> QuantumGame {
+        QuantumGame {
+            wins: vec![None; 10 * 10 * QUANTUM_TARGET as usize * QUANTUM_TARGET as usize],
+        }
+    }
+
+    fn wins(&mut self, position: u8, other_position: u8, score: u8, other_score: u8) -> (u64, u64) {
+        let key = (((position as usize - 1) * 10 + other_position as usize - 1)
+            * QUANTUM_TARGET as usize
+            + score as usize)
+            * QUANTUM_TARGET as usize
+            + other_score as usize;
+        if let Some(wins) = self.wins[key] {
+            return wins;
+        }
+
+        let (mut mine, mut theirs) = (0, 0);
+        for (roll, universes) in QUANTUM_ROLLS {
+            let position = advance(position, roll);
+            let score = score + position;
+            if score >= QUANTUM_TARGET {
+                mine += universes;
+            } else {
+                // The other player moves next, so their wins come first.
+                let (next_theirs, next_mine) =
+                    self.wins(other_position, position, other_score, score);
+                mine += universes * next_mine;
+                theirs += universes * next_theirs;
+            }
+        }
+        self.wins[key] = Some((mine, theirs));
+        (mine, theirs)
+    }
+}
+
+#[derive(Default)]
+pub struct Problem21 {}
+
+impl Problem21 {
+    pub fn new() -> Problem21 {
+        Problem21 {}
+    }
+
+    pub fn solve_actual(&self, starting_positions: &[u8; 2]) -> i64 {
+        let mut positions = *starting_positions;
+        let mut scores = [0u64; 2];
+        let mut rolls = 0u64;
+        let mut player = 0;
+        loop {
+            // The deterministic die rolls 1 to 100 and wraps back around.
+            let roll: u64 = (0..3)
+                .map(|_| {
+                    rolls += 1;
+                    (rolls - 1) % 100 + 1
+                })
+                .sum();
+            positions[player] = advance(positions[player], roll);
+            scores[player] += positions[player] as u64;
+            if scores[player] >= 1000 {
+                return (scores[1 - player] * rolls) as i64;
+            }
+            player = 1 - player;
+        }
+    }
+
+    pub fn solve_actual_part2(&self, starting_positions: &[u8; 2]) -> i64 {
+        let (first, second) =
+            QuantumGame::new().wins(starting_positions[0], starting_positions[1], 0, 0);
+        first.max(second) as i64
+    }
+}
+
+impl Problem for Problem21 {
+    type Parsed = [u8; 2];
+
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    fn day(&self) -> u8 {
+        Self::DAY
+    }
+
+    fn default_input(&self) -> Result<String, AocError> {
+        get_input!("./inputs/problem_21.txt")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, AocError> {
+        let mut positions = [0; 2];
+        let mut lines = input.lines().enumerate();
+        for (player, position) in positions.iter_mut().enumerate() {
+            let prefix = format!("Player {} starting position: ", player + 1);
+            let (index, line) = lines.next().unwrap_or((player, ""));
+            *position = match line.strip_prefix(&prefix) {
+                Some(token) => parse_token(index, line, token)?,
+                None => {
+                    return Err(AocError::parse(
+                        index + 1,
+                        1,
+                        format!("expected '{}N'", prefix),
+                    ))
+                }
+            };
+            if !(1..=10).contains(position) {
+                return Err(AocError::parse(
+                    index + 1,
+                    prefix.len() + 1,
+                    format!("expected a position from 1 to 10, found {}", position),
+                ));
+            }
+        }
+        Ok(positions)
+    }
+
+    fn part1(&self, starting_positions: &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual(starting_positions).into())
+    }
+
+    fn part2(&self, starting_positions: &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual_part2(starting_positions).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quantum_rolls_cover_every_universe() {
+        assert_eq!(
+            QUANTUM_ROLLS.iter().map(|(_, count)| count).sum::<u64>(),
+            27
+        );
+    }
+
+    #[test]
+    fn test_solve_actual_from_example() {
+        let problem = Problem21::new();
+        let input = get_input!("./inputs/problem_21_example.txt").unwrap();
+        let starting_positions = problem.parse(&input).unwrap();
+        assert_eq!(problem.solve_actual(&starting_positions), 739785);
+    }
+
+    #[test]
+    fn test_solve_actual_from_input() {
+        let problem = Problem21::new();
+        let input = get_input!("./inputs/problem_21.txt").unwrap();
+        let starting_positions = problem.parse(&input).unwrap();
+        assert_eq!(problem.solve_actual(&starting_positions), 551901);
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_positions() {
+        let problem = Problem21::new();
+        match problem.parse("Player 1 starting position: 4\nPlayer 2 starting position: 11") {
+            Err(AocError::Parse { line, column, .. }) => assert_eq!((line, column), (2, 29)),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+        assert!(problem.parse("Player 1 starting position: 4").is_err());
+        assert!(problem
+            .parse("Player 2 starting position: 4\nPlayer 1 starting position: 8")
+            .is_err());
+        assert!(problem
+            .parse("Player 1 starting position: x\nPlayer 2 starting position: 8")
+            .is_err());
+    }
+
+    #[test]
+    fn test_solve_actual_part2_from_example() {
+        let problem = Problem21::new();
+        let input = get_input!("./inputs/problem_21_example.txt").unwrap();
+        let starting_positions = problem.parse(&input).unwrap();
+        assert_eq!(
+            problem.solve_actual_part2(&starting_positions),
+            444356092776315
+        );
+    }
+
+    #[test]
+    fn test_solve_actual_part2_from_input() {
+        let problem = Problem21::new();
+        let input = get_input!("./inputs/problem_21.txt").unwrap();
+        let starting_positions = problem.parse(&input).unwrap();
+        assert_eq!(
+            problem.solve_actual_part2(&starting_positions),
+            272847859601291
+        );
+    }
+}
@@ -0,0 +1,266 @@
+use std::collections::HashMap;
+
+use crate::{
+    answer::Answer,
+    error::AocError,
+    problem::Problem,
+    util::{column_of, parse_token},
+};
+
+/// An axis-aligned box of cubes, inclusive on both ends of every axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cuboid {
+    min: [i64; 3],
+    max: [i64; 3],
+}
+
+impl Cuboid {
+    /// Returns `None` if any axis is backwards.
+    pub fn new(min: [i64; 3], max: [i64; 3]) -> Option<Cuboid> {
+        match (0..3).all(|axis| min[axis] <= max[axis]) {
+            true => Some(Cuboid { min, max }),
+            false => None,
+        }
+    }
+
+    pub fn volume(&self) -> i64 {
+        (0..3)
+            .map(|axis| self.max[axis] - self.min[axis] + 1)
+            .product()
+    }
+
+    pub fn intersection(&self, other: &Cuboid) -> Option<Cuboid> {
+        Cuboid::new(
+            [0, 1, 2].map(|axis| self.min[axis].max(other.min[axis])),
+            [0, 1, 2].map(|axis| self.max[axis].min(other.max[axis])),
+        )
+    }
+}
+
+pub struct RebootStep {
+    on: bool,
+    cuboid: Cuboid,
+}
+
+#[derive(Default)]
+pub struct Problem22 {}
+
+impl Problem22 {
+    pub fn new() -> Problem22 {
+        Problem22 {}
+    }
+
+    fn parse_range(&self, index: usize, line: &str, range: &str) -> Result<(i64, i64), AocError> {
+        match range.get(2..).and_then(|range| range.split_once("..")) {
+            Some((start, end)) => Ok((
+                parse_token(index, line, start)?,
+                parse_token(index, line, end)?,
+            )),
+            None => Err(AocError::parse(
+                index + 1,
+                column_of(line, range),
+                format!("expected a range like 'x=a..b', found '{}'", range),
+            )),
+        }
+    }
+
+    /// Counts the lit cubes after every step. Each step cancels out what
+    /// it overlaps by adding every counted cuboid's intersection with it
+    /// with the opposite sign, then counts itself if it turns cubes on.
+    /// Identical cuboids share one entry, which keeps the list small.
+    fn count_lit(&self, steps: impl Iterator<Item = (bool, Cuboid)>) -> i64 {
+        let mut counted: HashMap<Cuboid, i64> = HashMap::new();
+        for (on, cuboid) in steps {
+            let mut changes: HashMap<Cuboid, i64> = HashMap::new();
+            for (existing, sign) in &counted {
+                if let Some(overlap) = existing.intersection(&cuboid) {
+                    *changes.entry(overlap).or_default() -= sign;
+                }
+            }
+            if on {
+                *changes.entry(cuboid).or_default() += 1;
+            }
+            for (cuboid, change) in changes {
+                *counted.entry(cuboid).or_default() += change;
+            }
+            counted.retain(|_, sign| *sign != 0);
+        }
+        counted
+            .iter()
+            .map(|(cuboid, sign)| cuboid.volume() * sign)
+            .sum()
+    }
+
+    pub fn solve_actual(&self, steps: &[RebootStep]) -> i64 {
+        let region = Cuboid {
+            min: [-50; 3],
+            max: [50; 3],
+        };
+        self.count_lit(steps.iter().filter_map(|step| {
+            step.cuboid
+                .intersection(&region)
+                .map(|cuboid| (step.on, cuboid))
+        }))
+    }
+
+    pub fn solve_actual_part2(&self, steps: &[RebootStep]) -> i64 {
+        self.count_lit(steps.iter().map(|step| (step.on, step.cuboid)))
+    }
+}
+
+impl Problem for Problem22 {
+    type Parsed = Vec<RebootStep>;
+
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    fn day(&self) -> u8 {
+        Self::DAY
+    }
+
+    fn default_input(&self) -> Result<String, AocError> {
+        get_input!("./inputs/problem_22.txt")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, AocError> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(index, line)| {
+                let (on, ranges) = match line.split_once(' ') {
+                    Some(("on", ranges)) => (true, ranges),
+                    Some(("off", ranges)) => (false, ranges),
+                    _ => {
+                        return Err(AocError::parse(
+                            index + 1,
+                            1,
+                            "expected 'on' or 'off' followed by x, y and z ranges",
+                        ))
+                    }
+                };
+                let ranges: Vec<&str> = ranges.split(',').collect();
+                let (x, y, z) = match ranges[..] {
+                    [x, y, z]
+                        if x.starts_with("x=") && y.starts_with("y=") && z.starts_with("z=") =>
+                    {
+                        (
+                            self.parse_range(index, line, x)?,
+                            self.parse_range(index, line, y)?,
+                            self.parse_range(index, line, z)?,
+                        )
+                    }
+                    _ => {
+                        return Err(AocError::parse(
+                            index + 1,
+                            column_of(line, ranges[0]),
+                            "expected 'x=a..b,y=c..d,z=e..f'",
+                        ))
+                    }
+                };
+                match Cuboid::new([x.0, y.0, z.0], [x.1, y.1, z.1]) {
+                    Some(cuboid) => Ok(RebootStep { on, cuboid }),
+                    None => Err(AocError::parse(
+                        index + 1,
+                        column_of(line, ranges[0]),
+                        "ranges must not be backwards",
+                    )),
+                }
+            })
+            .collect()
+    }
+
+    fn part1(&self, steps: &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual(steps).into())
+    }
+
+    fn part2(&self, steps: &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual_part2(steps).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cuboid_volume_and_intersection() {
+        let first = Cuboid::new([10, 10, 10], [12, 12, 12]).unwrap();
+        let second = Cuboid::new([11, 11, 11], [13, 13, 13]).unwrap();
+        assert_eq!(first.volume(), 27);
+        assert_eq!(Cuboid::new([1, 1, 1], [1, 1, 1]).unwrap().volume(), 1);
+        assert_eq!(
+            first.intersection(&second),
+            Cuboid::new([11, 11, 11], [12, 12, 12])
+        );
+        assert_eq!(first.intersection(&second), second.intersection(&first));
+        // Touching along a face still shares the cubes on that face.
+        let touching = Cuboid::new([12, 0, 0], [20, 20, 20]).unwrap();
+        assert_eq!(first.intersection(&touching).unwrap().volume(), 9);
+        let apart = Cuboid::new([13, 10, 10], [14, 12, 12]).unwrap();
+        assert_eq!(first.intersection(&apart), None);
+        assert_eq!(Cuboid::new([0, 0, 1], [0, 0, 0]), None);
+    }
+
+    #[test]
+    fn test_solve_actual_from_example_01() {
+        let problem = Problem22::new();
+        let input = get_input!("./inputs/problem_22_example_01.txt").unwrap();
+        let steps = problem.parse(&input).unwrap();
+        assert_eq!(problem.solve_actual(&steps), 39);
+    }
+
+    #[test]
+    fn test_solve_actual_from_example_02() {
+        let problem = Problem22::new();
+        let input = get_input!("./inputs/problem_22_example_02.txt").unwrap();
+        let steps = problem.parse(&input).unwrap();
+        // Only the first ten steps are inside the initialization region.
+        assert_eq!(problem.solve_actual(&steps), 474140);
+    }
+
+    #[test]
+    fn test_solve_actual_from_input() {
+        let problem = Problem22::new();
+        let input = get_input!("./inputs/problem_22.txt").unwrap();
+        let steps = problem.parse(&input).unwrap();
+        assert_eq!(problem.solve_actual(&steps), 176670);
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_steps() {
+        let problem = Problem22::new();
+        match problem.parse("on x=10..12,y=10..1a,z=10..12") {
+            Err(AocError::Parse { line, column, .. }) => assert_eq!((line, column), (1, 19)),
+            other => panic!("expected a parse error, got {:?}", other.map(|s| s.len())),
+        }
+        assert!(problem.parse("toggle x=1..2,y=1..2,z=1..2").is_err());
+        assert!(problem.parse("on x=1..2,y=1..2").is_err());
+        assert!(problem.parse("on x=1..2,z=1..2,y=1..2").is_err());
+        assert!(problem.parse("off x=2..1,y=1..2,z=1..2").is_err());
+    }
+
+    #[test]
+    fn test_solve_actual_part2_from_example_01() {
+        let problem = Problem22::new();
+        let input = get_input!("./inputs/problem_22_example_01.txt").unwrap();
+        let steps = problem.parse(&input).unwrap();
+        assert_eq!(problem.solve_actual_part2(&steps), 39);
+    }
+
+    #[test]
+    fn test_solve_actual_part2_from_example_02() {
+        let problem = Problem22::new();
+        let input = get_input!("./inputs/problem_22_example_02.txt").unwrap();
+        let steps = problem.parse(&input).unwrap();
+        assert_eq!(problem.solve_actual_part2(&steps), 2758514936282235);
+    }
+
+    #[test]
+    fn test_solve_actual_part2_from_input() {
+        let problem = Problem22::new();
+        let input = get_input!("./inputs/problem_22.txt").unwrap();
+        let steps = problem.parse(&input).unwrap();
+        assert_eq!(problem.solve_actual_part2(&steps), 845407731116104);
+    }
+}
@@ -0,0 +1,379 @@
+use crate::{
+    answer::Answer,
+    error::AocError,
+    problem::Problem,
+    search::{astar, Path},
+};
+
+const KINDS: [char; 4] = ['A', 'B', 'C', 'D'];
+const ENERGY: [i64; 4] = [1, 10, 100, 1000];
+
+const HALLWAY_LENGTH: usize = 11;
+/// Hallway spaces an amphipod can stop in. The rest are right outside a
+/// room.
+const HALLWAY_STOPS: [usize; 7] = [0, 1, 3, 5, 7, 9, 10];
+
+/// The rows part 2 finds folded up in the middle of every room.
+const UNFOLDED_ROWS: [[u8; 4]; 2] = [[4, 3, 2, 1], [4, 2, 1, 3]];
+
+fn room_entrance(room: usize) -> usize {
+    2 + 2 * room
+}
+
+/// A burrow's layout, with states packed into a `u128`. Every space is 3
+/// bits: 0 when empty, or 1 to 4 for an A to D amphipod. The seven hallway
+/// stops come first, then each room from the top down.
+#[derive(Clone)]
+pub struct Burrow {
+    depth: usize,
+    start: u128,
+}
+
+impl Burrow {
+    fn new(rooms: &[[u8; 4]]) -> Burrow {
+        let mut burrow = Burrow {
+            depth: rooms.len(),
+            start: 0,
+        };
+        for (row, kinds) in rooms.iter().enumerate() {
+            for (room, kind) in kinds.iter().enumerate() {
+                burrow.start = burrow.set(burrow.start, burrow.room_space(room, row), *kind);
+            }
+        }
+        burrow
+    }
+
+    fn room_space(&self, room: usize, row: usize) -> usize {
+        HALLWAY_STOPS.len() + room * self.depth + row
+    }
+
+    fn get(&self, state: u128, space: usize) -> u8 {
+        ((state >> (3 * space)) & 0b111) as u8
+    }
+
+    fn set(&self, state: u128, space: usize, kind: u8) -> u128 {
+        (state & !(0b111 << (3 * space))) | ((kind as u128) << (3 * space))
+    }
+
+    fn organized(&self) -> u128 {
+        (0..4).fold(0, |state, room| {
+            (0..self.depth).fold(state, |state, row| {
+                self.set(state, self.room_space(room, row), room as u8 + 1)
+            })
+        })
+    }
+
+    /// Part 2's burrow: the two hidden rows go between the first and last.
+    pub fn unfold(&self) -> Burrow {
+        let rooms: Vec<[u8; 4]> = (0..self.depth)
+            .map(|row| [0, 1, 2, 3].map(|room| self.get(self.start, self.room_space(room, row))))
+            .collect();
+        let (first, rest) = rooms.split_at(1);
+        Burrow::new(&[first, &UNFOLDED_ROWS, rest].concat())
+    }
+
+    /// Whether the hallway is empty from `from` to `to`, not counting `from`
+    /// itself.
+    fn hallway_clear(&self, state: u128, from: usize, to: usize) -> bool {
+        HALLWAY_STOPS.iter().enumerate().all(|(stop, x)| {
+            *x == from || !(from.min(to)..=from.max(to)).contains(x) || self.get(state, stop) == 0
+        })
+    }
+
+    /// Every state reachable in one move, with the energy it takes. An
+    /// amphipod either leaves its room for a hallway stop, or goes from the
+    /// hallway to the bottom of its own room once no strangers are in it.
+    fn moves(&self, state: u128) -> Vec<(u128, i64)> {
+        let mut moves = Vec::new();
+        for room in 0..4 {
+            let kinds: Vec<u8> = (0..self.depth)
+                .map(|row| self.get(state, self.room_space(room, row)))
+                .collect();
+            if kinds
+                .iter()
+                .all(|kind| *kind == 0 || *kind == room as u8 + 1)
+            {
+                continue;
+            }
+            let Some(row) = kinds.iter().position(|kind| *kind != 0) else {
+                continue;
+            };
+            let kind = kinds[row];
+            let from = self.room_space(room, row);
+            for (stop, x) in HALLWAY_STOPS.iter().enumerate() {
+                if self.hallway_clear(state, room_entrance(room), *x) {
+                    let steps = (row + 1 + room_entrance(room).abs_diff(*x)) as i64;
+                    moves.push((
+                        self.set(self.set(state, from, 0), stop, kind),
+                        steps * ENERGY[kind as usize - 1],
+                    ));
+                }
+            }
+        }
+
+        for (stop, x) in HALLWAY_STOPS.iter().enumerate() {
+            let kind = self.get(state, stop);
+            if kind == 0 {
+                continue;
+            }
+            let room = kind as usize - 1;
+            let kinds: Vec<u8> = (0..self.depth)
+                .map(|row| self.get(state, self.room_space(room, row)))
+                .collect();
+            if kinds.iter().any(|other| *other != 0 && *other != kind)
+                || !self.hallway_clear(state, *x, room_entrance(room))
+            {
+                continue;
+            }
+            let Some(row) = kinds.iter().rposition(|other| *other == 0) else {
+                continue;
+            };
+            let steps = (row + 1 + room_entrance(room).abs_diff(*x)) as i64;
+            moves.push((
+                self.set(self.set(state, stop, 0), self.room_space(room, row), kind),
+                steps * ENERGY[room],
+            ));
+        }
+        moves
+    }
+
+    /// A lower bound on the energy left to spend: every amphipod that isn't
+    /// settled has to at least walk to right above its own room and step
+    /// in. One that's in its own room but on top of strangers has to step
+    /// out and back, too.
+    fn estimate(&self, state: u128) -> i64 {
+        let mut estimate = 0;
+        for (stop, x) in HALLWAY_STOPS.iter().enumerate() {
+            let kind = self.get(state, stop) as usize;
+            if kind != 0 {
+                let steps = room_entrance(kind - 1).abs_diff(*x) + 1;
+                estimate += steps as i64 * ENERGY[kind - 1];
+            }
+        }
+        for room in 0..4 {
+            let mut settled = true;
+            for row in (0..self.depth).rev() {
+                let kind = self.get(state, self.room_space(room, row)) as usize;
+                if kind == 0 {
+                    continue;
+                }
+                settled &= kind == room + 1;
+                if !settled {
+                    let steps = row + 1 + room_entrance(kind - 1).abs_diff(room_entrance(room));
+                    estimate += (steps.max(row + 3) + 1) as i64 * ENERGY[kind - 1];
+                }
+            }
+        }
+        estimate
+    }
+
+    /// The cheapest way from the starting state to the organized one: every
+    /// state along the way, which `render` can draw, with the energy spent
+    /// to reach it.
+    pub fn optimal_moves(&self) -> Result<Path<u128>, AocError> {
+        let goal = self.organized();
+        astar(
+            self.start,
+            |state| self.moves(*state),
+            |state| *state == goal,
+            |state| self.estimate(*state),
+        )
+        .ok_or_else(|| AocError::no_solution("the amphipods can't be organized"))
+    }
+
+    /// Draws a state the way the puzzle does.
+    pub fn render(&self, state: u128) -> String {
+        let symbol = |kind: u8| match kind {
+            0 => '.',
+            kind => KINDS[kind as usize - 1],
+        };
+        let mut hallway = ['.'; HALLWAY_LENGTH];
+        for (stop, x) in HALLWAY_STOPS.iter().enumerate() {
+            hallway[*x] = symbol(self.get(state, stop));
+        }
+        let mut lines = vec![
+            "#############".to_string(),
+            format!("#{}#", hallway.iter().collect::<String>()),
+        ];
+        for row in 0..self.depth {
+            let kinds: Vec<String> = (0..4)
+                .map(|room| symbol(self.get(state, self.room_space(room, row))).to_string())
+                .collect();
+            lines.push(match row {
+                0 => format!("###{}###", kinds.join("#")),
+                _ => format!("  #{}#", kinds.join("#")),
+            });
+        }
+        lines.push("  #########".to_string());
+        lines.join("\n")
+    }
+}
+
+#[derive(Default)]
+pub struct Problem23 {}
+
+impl Problem23 {
+    pub fn new() -> Problem23 {
+        Problem23 {}
+    }
+
+    pub fn solve_actual(&self, burrow: &Burrow) -> Result<i64, AocError> {
+        Ok(burrow.optimal_moves()?.cost)
+    }
+
+    pub fn solve_actual_part2(&self, burrow: &Burrow) -> Result<i64, AocError> {
+        self.solve_actual(&burrow.unfold())
+    }
+}
+
+impl Problem for Problem23 {
+    type Parsed = Burrow;
+
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    fn day(&self) -> u8 {
+        Self::DAY
+    }
+
+    fn default_input(&self) -> Result<String, AocError> {
+        get_input!("./inputs/problem_23.txt")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, AocError> {
+        let lines: Vec<&str> = input.lines().collect();
+        if lines.len() != 5 || lines[0] != "#############" || lines[4].trim() != "#########" {
+            return Err(AocError::parse(
+                1,
+                1,
+                "expected a burrow with a hallway and two rows of rooms",
+            ));
+        }
+        if lines[1] != "#...........#" {
+            return Err(AocError::parse(2, 1, "expected an empty hallway"));
+        }
+
+        let mut rooms = [[0; 4]; 2];
+        for (row, kinds) in rooms.iter_mut().enumerate() {
+            let line = lines[row + 2];
+            for (room, kind) in kinds.iter_mut().enumerate() {
+                let column = 3 + 2 * room;
+                *kind = match line.chars().nth(column) {
+                    Some(c @ 'A'..='D') => c as u8 - b'A' + 1,
+                    found => {
+                        return Err(AocError::parse(
+                            row + 3,
+                            column + 1,
+                            format!("expected an amphipod, found {:?}", found),
+                        ))
+                    }
+                };
+            }
+        }
+        for kind in 1..=4 {
+            if rooms
+                .iter()
+                .flatten()
+                .filter(|other| **other == kind)
+                .count()
+                != 2
+            {
+                return Err(AocError::parse(
+                    3,
+                    1,
+                    format!("expected two {} amphipods", KINDS[kind as usize - 1]),
+                ));
+            }
+        }
+        Ok(Burrow::new(&rooms))
+    }
+
+    fn part1(&self, burrow: &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual(burrow)?.into())
+    }
+
+    fn part2(&self, burrow: &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual_part2(burrow)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_and_unfold() {
+        let problem = Problem23::new();
+        let input = get_input!("./inputs/problem_23_example.txt").unwrap();
+        let burrow = problem.parse(&input).unwrap();
+        assert_eq!(burrow.render(burrow.start), input);
+        assert_eq!(
+            burrow.unfold().render(burrow.unfold().start),
+            "#############\n#...........#\n###B#C#B#D###\n  #D#C#B#A#\n  #D#B#A#C#\n  #A#D#C#A#\n  #########"
+        );
+    }
+
+    #[test]
+    fn test_optimal_moves_from_example() {
+        let problem = Problem23::new();
+        let input = get_input!("./inputs/problem_23_example.txt").unwrap();
+        let burrow = problem.parse(&input).unwrap();
+        let path = burrow.optimal_moves().unwrap();
+        assert_eq!(path.nodes.first(), Some(&(burrow.start, 0)));
+        assert_eq!(path.nodes.last(), Some(&(burrow.organized(), path.cost)));
+        // Every step along the path is a single legal move.
+        for window in path.nodes.windows(2) {
+            let ((from, before), (to, after)) = (window[0], window[1]);
+            assert!(burrow.moves(from).contains(&(to, after - before)));
+        }
+    }
+
+    #[test]
+    fn test_solve_actual_from_example() {
+        let problem = Problem23::new();
+        let input = get_input!("./inputs/problem_23_example.txt").unwrap();
+        let burrow = problem.parse(&input).unwrap();
+        assert_eq!(problem.solve_actual(&burrow).unwrap(), 12521);
+    }
+
+    #[test]
+    fn test_solve_actual_from_input() {
+        let problem = Problem23::new();
+        let input = get_input!("./inputs/problem_23.txt").unwrap();
+        let burrow = problem.parse(&input).unwrap();
+        assert_eq!(problem.solve_actual(&burrow).unwrap(), 15608);
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_burrows() {
+        let problem = Problem23::new();
+        match problem.parse("#############\n#...........#\n###B#C#B#D###\n  #A#D#X#A#\n  #########")
+        {
+            Err(AocError::Parse { line, column, .. }) => assert_eq!((line, column), (4, 8)),
+            other => panic!("expected a parse error, got {:?}", other.map(|_| ())),
+        }
+        assert!(problem
+            .parse("#############\n#...........#\n###B#C#B#D###\n  #A#D#C#B#\n  #########")
+            .is_err());
+        assert!(problem
+            .parse("#############\n#..A........#\n###B#C#B#D###\n  #.#D#C#A#\n  #########")
+            .is_err());
+    }
+
+    #[test]
+    fn test_solve_actual_part2_from_example() {
+        let problem = Problem23::new();
+        let input = get_input!("./inputs/problem_23_example.txt").unwrap();
+        let burrow = problem.parse(&input).unwrap();
+        assert_eq!(problem.solve_actual_part2(&burrow).unwrap(), 44169);
+    }
+
+    #[test]
+    fn test_solve_actual_part2_from_input() {
+        let problem = Problem23::new();
+        let input = get_input!("./inputs/problem_23.txt").unwrap();
+        let burrow = problem.parse(&input).unwrap();
+        assert_eq!(problem.solve_actual_part2(&burrow).unwrap(), 46574);
+    }
+}
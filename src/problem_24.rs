@@ -0,0 +1,423 @@
+use std::fmt;
+
+use crate::{
+    answer::Answer,
+    error::AocError,
+    problem::Problem,
+    util::{column_of, parse_token},
+};
+
+const REGISTERS: [&str; 4] = ["w", "x", "y", "z"];
+
+/// MONAD repeats one 18 instruction block per digit. The blocks only differ
+/// in three literals, at these offsets.
+const BLOCK_LENGTH: usize = 18;
+const DIVISOR_OFFSET: usize = 4;
+const CHECK_OFFSET: usize = 5;
+const OFFSET_OFFSET: usize = 15;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand {
+    Register(usize),
+    Literal(i64),
+}
+
+/// One ALU instruction. Registers are indexes into `Alu::registers`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Inp(usize),
+    Add(usize, Operand),
+    Mul(usize, Operand),
+    Div(usize, Operand),
+    Mod(usize, Operand),
+    Eql(usize, Operand),
+}
+
+/// Ways an ALU program can fail partway through, by 0-indexed instruction.
+#[derive(Debug, PartialEq, Eq)]
+pub enum AluError {
+    OutOfInput { instruction: usize },
+    DivideByZero { instruction: usize },
+    InvalidModulo { instruction: usize },
+    /// The result didn't fit in a register.
+    Overflow { instruction: usize },
+}
+
+impl fmt::Display for AluError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AluError::OutOfInput { instruction } => {
+                write!(
+                    f,
+                    "instruction {} read past the end of the input",
+                    instruction + 1
+                )
+            }
+            AluError::DivideByZero { instruction } => {
+                write!(f, "instruction {} divided by zero", instruction + 1)
+            }
+            AluError::InvalidModulo { instruction } => write!(
+                f,
+                "instruction {} took a negative number modulo a non-positive one",
+                instruction + 1
+            ),
+            AluError::Overflow { instruction } => {
+                write!(f, "instruction {} overflowed", instruction + 1)
+            }
+        }
+    }
+}
+
+/// The four registers of the submarine's arithmetic logic unit.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Alu {
+    pub registers: [i64; 4],
+}
+
+impl Alu {
+    /// Runs `program` from zeroed registers, reading `inputs` in order.
+    pub fn run(program: &[Instruction], inputs: &[i64]) -> Result<Alu, AluError> {
+        let mut alu = Alu::default();
+        let mut inputs = inputs.iter();
+        for (instruction, step) in program.iter().enumerate() {
+            let (target, value) = match *step {
+                Instruction::Inp(target) => match inputs.next() {
+                    Some(value) => (target, *value),
+                    None => return Err(AluError::OutOfInput { instruction }),
+                },
+                Instruction::Add(target, operand) => (
+                    target,
+                    alu.registers[target]
+                        .checked_add(alu.value(operand))
+                        .ok_or(AluError::Overflow { instruction })?,
+                ),
+                Instruction::Mul(target, operand) => (
+                    target,
+                    alu.registers[target]
+                        .checked_mul(alu.value(operand))
+                        .ok_or(AluError::Overflow { instruction })?,
+                ),
+                Instruction::Div(target, operand) => match alu.value(operand) {
+                    0 => return Err(AluError::DivideByZero { instruction }),
+                    divisor => (
+                        target,
+                        alu.registers[target]
+                            .checked_div(divisor)
+                            .ok_or(AluError::Overflow { instruction })?,
+                    ),
+                },
+                Instruction::Mod(target, operand) => {
+                    match (alu.registers[target], alu.value(operand)) {
+                        (value, divisor) if value >= 0 && divisor > 0 => (target, value % divisor),
+                        _ => return Err(AluError::InvalidModulo { instruction }),
+                    }
+                }
+                Instruction::Eql(target, operand) => {
+                    (target, (alu.registers[target] == alu.value(operand)) as i64)
+                }
+            };
+            alu.registers[target] = value;
+        }
+        Ok(alu)
+    }
+
+    fn value(&self, operand: Operand) -> i64 {
+        match operand {
+            Operand::Register(register) => self.registers[register],
+            Operand::Literal(value) => value,
+        }
+    }
+}
+
+impl fmt::Display for Alu {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let registers: Vec<String> = REGISTERS
+            .iter()
+            .zip(self.registers)
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect();
+        write!(f, "{}", registers.join(" "))
+    }
+}
+
+#[derive(Default)]
+pub struct Problem24 {}
+
+impl Problem24 {
+    pub fn new() -> Problem24 {
+        Problem24 {}
+    }
+
+    /// Reads the literals that set each digit's block apart. Returns the
+    /// divisor of `z`, the amount added to `x` before comparing it to the
+    /// digit, and the amount added to the digit before pushing it.
+    fn blocks(&self, program: &[Instruction]) -> Result<Vec<(i64, i64, i64)>, AocError> {
+        let not_monad = || AocError::no_solution("the program doesn't look like MONAD");
+        if program.len() != 14 * BLOCK_LENGTH {
+            return Err(not_monad());
+        }
+        program
+            .chunks(BLOCK_LENGTH)
+            .map(|block| {
+                match (
+                    block[0],
+                    block[DIVISOR_OFFSET],
+                    block[CHECK_OFFSET],
+                    block[OFFSET_OFFSET],
+                ) {
+                    (
+                        Instruction::Inp(0),
+                        Instruction::Div(3, Operand::Literal(divisor @ (1 | 26))),
+                        Instruction::Add(1, Operand::Literal(check)),
+                        Instruction::Add(2, Operand::Literal(offset)),
+                    ) => Ok((divisor, check, offset)),
+                    _ => Err(not_monad()),
+                }
+            })
+            .collect()
+    }
+
+    /// Treats `z` as a stack of base 26 digits. A block that divides by 1
+    /// pushes its digit plus its offset, since its check can never match a
+    /// digit. A block that divides by 26 pops, and only pushes nothing back
+    /// when its digit equals the popped value plus its check. `z` ends at 0
+    /// when every pop matches, which pairs up the digits: for every push `i`
+    /// and its pop `j`, digit `j` = digit `i` + offset `i` + check `j`.
+    /// `choose` picks the two digits of each pair from that difference.
+    fn model_number(
+        &self,
+        program: &[Instruction],
+        choose: fn(i64) -> (i64, i64),
+    ) -> Result<i64, AocError> {
+        let mut digits = [0; 14];
+        let mut stack = Vec::new();
+        for (index, (divisor, check, offset)) in self.blocks(program)?.into_iter().enumerate() {
+            if divisor == 1 {
+                stack.push((index, offset));
+                continue;
+            }
+            let Some((pushed, pushed_offset)) = stack.pop() else {
+                return Err(AocError::no_solution(format!(
+                    "digit {} pops from an empty stack",
+                    index + 1
+                )));
+            };
+            let difference = pushed_offset + check;
+            if difference.abs() > 8 {
+                return Err(AocError::no_solution(format!(
+                    "digits {} and {} can't differ by {}",
+                    pushed + 1,
+                    index + 1,
+                    difference
+                )));
+            }
+            (digits[pushed], digits[index]) = choose(difference);
+        }
+        if !stack.is_empty() {
+            return Err(AocError::no_solution("more digits push than pop"));
+        }
+
+        // Check the derived number against the program itself.
+        let alu = Alu::run(program, &digits).map_err(|e| AocError::no_solution(e.to_string()))?;
+        let number = digits.iter().fold(0, |number, digit| number * 10 + digit);
+        match alu.registers[3] {
+            0 => Ok(number),
+            _ => Err(AocError::no_solution(format!(
+                "MONAD rejected {} ({})",
+                number, alu
+            ))),
+        }
+    }
+
+    pub fn solve_actual(&self, program: &[Instruction]) -> Result<i64, AocError> {
+        self.model_number(program, |difference| {
+            (9 - difference.max(0), 9 + difference.min(0))
+        })
+    }
+
+    pub fn solve_actual_part2(&self, program: &[Instruction]) -> Result<i64, AocError> {
+        self.model_number(program, |difference| {
+            (1 - difference.min(0), 1 + difference.max(0))
+        })
+    }
+
+    fn parse_register(&self, index: usize, line: &str, token: &str) -> Result<usize, AocError> {
+        REGISTERS
+            .iter()
+            .position(|name| *name == token)
+            .ok_or_else(|| {
+                AocError::parse(
+                    index + 1,
+                    column_of(line, token),
+                    format!("expected a register, found '{}'", token),
+                )
+            })
+    }
+}
+
+impl Problem for Problem24 {
+    type Parsed = Vec<Instruction>;
+
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    fn day(&self) -> u8 {
+        Self::DAY
+    }
+
+    fn default_input(&self) -> Result<String, AocError> {
+        get_input!("./inputs/problem_24.txt")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, AocError> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(index, line)| {
+                let tokens: Vec<&str> = line.split_ascii_whitespace().collect();
+                let operand = |token: &str| match self.parse_register(index, line, token) {
+                    Ok(register) => Ok(Operand::Register(register)),
+                    Err(_) => parse_token(index, line, token).map(Operand::Literal),
+                };
+                match tokens[..] {
+                    ["inp", target] => {
+                        Ok(Instruction::Inp(self.parse_register(index, line, target)?))
+                    }
+                    [name, target, value] => {
+                        let target = self.parse_register(index, line, target)?;
+                        let value = operand(value)?;
+                        match name {
+                            "add" => Ok(Instruction::Add(target, value)),
+                            "mul" => Ok(Instruction::Mul(target, value)),
+                            "div" => Ok(Instruction::Div(target, value)),
+                            "mod" => Ok(Instruction::Mod(target, value)),
+                            "eql" => Ok(Instruction::Eql(target, value)),
+                            _ => Err(AocError::parse(
+                                index + 1,
+                                1,
+                                format!("unknown instruction '{}'", name),
+                            )),
+                        }
+                    }
+                    _ => Err(AocError::parse(
+                        index + 1,
+                        1,
+                        "expected 'inp a' or an instruction with two operands",
+                    )),
+                }
+            })
+            .collect()
+    }
+
+    fn part1(&self, program: &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual(program)?.into())
+    }
+
+    fn part2(&self, program: &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual_part2(program)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alu_runs_example_program() {
+        let problem = Problem24::new();
+        let input = get_input!("./inputs/problem_24_example.txt").unwrap();
+        let program = problem.parse(&input).unwrap();
+        // The example writes the lowest four bits of its input to w, x, y
+        // and z, most significant first.
+        let alu = Alu::run(&program, &[13]).unwrap();
+        assert_eq!(alu.registers, [1, 1, 0, 1]);
+        assert_eq!(alu.to_string(), "w=1 x=1 y=0 z=1");
+        assert_eq!(Alu::run(&program, &[6]).unwrap().registers, [0, 1, 1, 0]);
+    }
+
+    #[test]
+    fn test_alu_errors() {
+        let problem = Problem24::new();
+        let program = problem.parse("inp x\nmul x -1\ninp z\neql z x").unwrap();
+        assert_eq!(
+            Alu::run(&program, &[3, -3]).unwrap().registers,
+            [0, -3, 0, 1]
+        );
+        assert_eq!(
+            Alu::run(&program, &[3]),
+            Err(AluError::OutOfInput { instruction: 2 })
+        );
+        let program = problem.parse("inp w\ndiv w x\nmod w 0").unwrap();
+        assert_eq!(
+            Alu::run(&program, &[1]),
+            Err(AluError::DivideByZero { instruction: 1 })
+        );
+        let program = problem.parse("inp w\nmod w 2").unwrap();
+        assert_eq!(
+            Alu::run(&program, &[-4]),
+            Err(AluError::InvalidModulo { instruction: 1 })
+        );
+        let program = problem.parse("inp w\nmul w w").unwrap();
+        assert_eq!(
+            Alu::run(&program, &[i64::MAX]),
+            Err(AluError::Overflow { instruction: 1 })
+        );
+        let program = problem.parse("inp w\nadd w 1").unwrap();
+        assert_eq!(
+            Alu::run(&program, &[i64::MAX]),
+            Err(AluError::Overflow { instruction: 1 })
+        );
+        let program = problem.parse("inp w\ndiv w -1").unwrap();
+        assert_eq!(
+            Alu::run(&program, &[i64::MIN]),
+            Err(AluError::Overflow { instruction: 1 })
+        );
+        // Division truncates towards zero.
+        let program = problem.parse("inp w\ndiv w 2").unwrap();
+        assert_eq!(Alu::run(&program, &[-7]).unwrap().registers[0], -3);
+    }
+
+    #[test]
+    fn test_solve_actual_from_input() {
+        let problem = Problem24::new();
+        let input = get_input!("./inputs/problem_24.txt").unwrap();
+        let program = problem.parse(&input).unwrap();
+        assert_eq!(problem.solve_actual(&program).unwrap(), 45799495959969);
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_instructions() {
+        let problem = Problem24::new();
+        match problem.parse("inp w\nadd x 1a") {
+            Err(AocError::Parse { line, column, .. }) => assert_eq!((line, column), (2, 7)),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+        assert!(problem.parse("inp q").is_err());
+        assert!(problem.parse("sub x 1").is_err());
+        assert!(problem.parse("add x").is_err());
+        assert!(problem.parse("add 1 x").is_err());
+    }
+
+    #[test]
+    fn test_solve_actual_rejects_other_programs() {
+        let problem = Problem24::new();
+        let input = get_input!("./inputs/problem_24_example.txt").unwrap();
+        let program = problem.parse(&input).unwrap();
+        assert!(matches!(
+            problem.solve_actual(&program),
+            Err(AocError::NoSolution { .. })
+        ));
+    }
+
+    #[test]
+    fn test_solve_actual_part2_from_input() {
+        let problem = Problem24::new();
+        let input = get_input!("./inputs/problem_24.txt").unwrap();
+        let program = problem.parse(&input).unwrap();
+        assert_eq!(
+            problem.solve_actual_part2(&program).unwrap(),
+            11135161515416
+        );
+    }
+}
@@ -0,0 +1,169 @@
+use std::collections::HashSet;
+
+use crate::{answer::Answer, error::AocError, grid::Grid, problem::Problem};
+
+const EMPTY: u8 = b'.';
+const EAST: u8 = b'>';
+const SOUTH: u8 = b'v';
+
+/// The sea floor, which wraps around on both axes.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct SeaFloor {
+    cells: Grid<u8>,
+}
+
+impl SeaFloor {
+    /// Moves every cucumber in `herd` that faces an empty cell, all at once.
+    /// Returns whether any of them moved.
+    fn move_herd(&mut self, herd: u8, row_step: usize, col_step: usize) -> bool {
+        let (width, height) = (self.cells.width(), self.cells.height());
+        let mut moves = Vec::new();
+        for row in 0..height {
+            for (col, cell) in self.cells.row(row).iter().enumerate() {
+                let target = ((row + row_step) % height, (col + col_step) % width);
+                if *cell == herd && self.cells[target] == EMPTY {
+                    moves.push(((row, col), target));
+                }
+            }
+        }
+        for (position, target) in &moves {
+            self.cells[*position] = EMPTY;
+            self.cells[*target] = herd;
+        }
+        !moves.is_empty()
+    }
+
+    /// One step: the east-facing herd moves first, then the south-facing
+    /// herd moves into whatever is empty afterwards.
+    fn step(&mut self) -> bool {
+        let east = self.move_herd(EAST, 0, 1);
+        let south = self.move_herd(SOUTH, 1, 0);
+        east || south
+    }
+
+    /// Draws every row the way the puzzle does.
+    pub fn render(&self) -> Vec<String> {
+        self.cells.render(|cell| *cell as char)
+    }
+}
+
+#[derive(Default)]
+pub struct Problem25 {}
+
+impl Problem25 {
+    pub fn new() -> Problem25 {
+        Problem25 {}
+    }
+
+    /// The first step on which no sea cucumber moves. The herds can also
+    /// go round in circles forever, which shows up as a repeated state.
+    pub fn solve_actual(&self, sea_floor: &SeaFloor) -> Result<i64, AocError> {
+        let mut sea_floor = sea_floor.clone();
+        let mut seen = HashSet::new();
+        let mut steps = 1;
+        while sea_floor.step() {
+            if !seen.insert(sea_floor.clone()) {
+                return Err(AocError::no_solution(
+                    "the sea cucumbers move in a cycle and never stop",
+                ));
+            }
+            steps += 1;
+        }
+        Ok(steps)
+    }
+}
+
+impl Problem for Problem25 {
+    type Parsed = SeaFloor;
+
+    fn name(&self) -> &str {
+        Self::NAME
+    }
+
+    fn day(&self) -> u8 {
+        Self::DAY
+    }
+
+    fn default_input(&self) -> Result<String, AocError> {
+        get_input!("./inputs/problem_25.txt")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, AocError> {
+        let cells = Grid::parse(input, "'.', '>' or 'v'", |c| {
+            matches!(c, '.' | '>' | 'v').then_some(c as u8)
+        })?;
+        Ok(SeaFloor { cells })
+    }
+
+    fn part1(&self, sea_floor: &Self::Parsed) -> Result<Answer, AocError> {
+        Ok(self.solve_actual(sea_floor)?.into())
+    }
+
+    /// Day 25 only has one puzzle. The second star comes for free with the
+    /// other 49.
+    fn part2(&self, _sea_floor: &Self::Parsed) -> Result<Answer, AocError> {
+        Ok("Merry Christmas!".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_step_wraps_around() {
+        let problem = Problem25::new();
+        let mut sea_floor = problem.parse("...>>>>>...").unwrap();
+        sea_floor.step();
+        assert_eq!(sea_floor.render(), vec!["...>>>>.>.."]);
+        sea_floor.step();
+        assert_eq!(sea_floor.render(), vec!["...>>>.>.>."]);
+
+        let mut sea_floor = problem.parse("..>\nv..\n.v.").unwrap();
+        sea_floor.step();
+        assert_eq!(sea_floor.render(), vec![">v.", "...", "v.."]);
+    }
+
+    #[test]
+    fn test_solve_actual_from_example() {
+        let problem = Problem25::new();
+        let input = get_input!("./inputs/problem_25_example.txt").unwrap();
+        let sea_floor = problem.parse(&input).unwrap();
+        assert_eq!(problem.solve_actual(&sea_floor).unwrap(), 58);
+    }
+
+    #[test]
+    fn test_solve_actual_from_input() {
+        let problem = Problem25::new();
+        let input = get_input!("./inputs/problem_25.txt").unwrap();
+        let sea_floor = problem.parse(&input).unwrap();
+        assert_eq!(problem.solve_actual(&sea_floor).unwrap(), 448);
+    }
+
+    #[test]
+    fn test_solve_actual_rejects_herds_that_never_stop() {
+        let problem = Problem25::new();
+        for input in [">.", "v\n.", ">.\n.v"] {
+            let sea_floor = problem.parse(input).unwrap();
+            assert!(
+                matches!(
+                    problem.solve_actual(&sea_floor),
+                    Err(AocError::NoSolution { .. })
+                ),
+                "{:?}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_maps() {
+        let problem = Problem25::new();
+        match problem.parse("..>\n.<v") {
+            Err(AocError::Parse { line, column, .. }) => assert_eq!((line, column), (2, 2)),
+            other => panic!("expected a parse error, got {:?}", other.map(|_| ())),
+        }
+        assert!(problem.parse("..>\n.v").is_err());
+        assert!(problem.parse("").is_err());
+    }
+}
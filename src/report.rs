@@ -0,0 +1,210 @@
+use std::{str::FromStr, time::Duration};
+
+use crate::{
+    answer::Answer,
+    runner::{PartResult, RunReport},
+    util::json_string,
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<OutputFormat, String> {
+        match value {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "csv" => Ok(OutputFormat::Csv),
+            _ => Err(format!(
+                "invalid format '{}', expected text, json or csv",
+                value
+            )),
+        }
+    }
+}
+
+// The time spent parsing and solving, summed over every day and part. Each
+// day is parsed once, so its parse time only counts towards the total on the
+// first of its parts.
+fn total_duration(results: &[PartResult]) -> Duration {
+    let mut previous_day = None;
+    let mut total = Duration::ZERO;
+    for result in results {
+        if previous_day != Some(result.day) {
+            total += result.parse_duration;
+            previous_day = Some(result.day);
+        }
+        total += result.duration;
+    }
+    total
+}
+
+fn render_text(report: &RunReport) -> String {
+    let results = &report.results;
+    let mut output = vec!["~ Advent of Code 2021 ~".to_string()];
+    let mut previous_day = None;
+    for result in results {
+        if previous_day != Some(result.day) {
+            output.push(result.name.clone());
+            output.push(format!(" - Parse: (took {:.2?})", result.parse_duration));
+            previous_day = Some(result.day);
+        }
+        output.push(match &result.result {
+            Ok(Answer::Grid(rows)) => format!(
+                " - Part {}: (took {:.2?})\n{}",
+                result.part,
+                result.duration,
+                rows.iter()
+                    .map(|row| format!("           {}", row))
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            ),
+            Ok(answer) => format!(
+                " - Part {}: {} (took {:.2?})",
+                result.part, answer, result.duration
+            ),
+            Err(error) => format!(" - Part {}: error: {}", result.part, error),
+        });
+    }
+    output.push(format!(
+        "Took a total of {:.2?} ({:.2?} wall clock)",
+        total_duration(results),
+        report.wall_clock
+    ));
+    output.join("\n")
+}
+
+fn render_json(report: &RunReport) -> String {
+    let results = &report.results;
+    let records: Vec<String> = results
+        .iter()
+        .map(|result| {
+            let (answer, answer_type, error) = match &result.result {
+                Ok(answer) => (answer.to_json(), json_string(answer.kind()), "null".to_string()),
+                Err(error) => ("null".to_string(), "null".to_string(), json_string(error)),
+            };
+            format!(
+                "    {{\"day\": {}, \"name\": {}, \"part\": {}, \"answer\": {}, \"answer_type\": {}, \"parse_duration_ns\": {}, \"duration_ns\": {}, \"error\": {}}}",
+                result.day,
+                json_string(&result.name),
+                result.part,
+                answer,
+                answer_type,
+                result.parse_duration.as_nanos(),
+                result.duration.as_nanos(),
+                error
+            )
+        })
+        .collect();
+    format!(
+        "{{\n  \"results\": [\n{}\n  ],\n  \"total_duration_ns\": {},\n  \"wall_clock_ns\": {}\n}}",
+        records.join(",\n"),
+        total_duration(results).as_nanos(),
+        report.wall_clock.as_nanos()
+    )
+}
+
+fn csv_field(value: &str) -> String {
+    match value.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", value.replace('"', "\"\"")),
+        false => value.to_string(),
+    }
+}
+
+fn render_csv(results: &[PartResult]) -> String {
+    let mut output = vec!["day,name,part,answer,parse_duration_ns,duration_ns,error".to_string()];
+    results.iter().for_each(|result| {
+        let (answer, error) = match &result.result {
+            Ok(answer) => (answer.to_string(), String::new()),
+            Err(error) => (String::new(), error.clone()),
+        };
+        output.push(format!(
+            "{},{},{},{},{},{},{}",
+            result.day,
+            csv_field(&result.name),
+            result.part,
+            csv_field(&answer),
+            result.parse_duration.as_nanos(),
+            result.duration.as_nanos(),
+            csv_field(&error)
+        ));
+    });
+    output.join("\n")
+}
+
+pub fn render(format: OutputFormat, report: &RunReport) -> String {
+    match format {
+        OutputFormat::Text => render_text(report),
+        OutputFormat::Json => render_json(report),
+        OutputFormat::Csv => render_csv(&report.results),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> RunReport {
+        let results = vec![
+            PartResult {
+                day: 4,
+                name: "Day 4: Giant Squid".to_string(),
+                part: 1,
+                result: Ok(Answer::Integer(4512)),
+                parse_duration: Duration::from_nanos(1000),
+                duration: Duration::from_nanos(1500),
+            },
+            PartResult {
+                day: 4,
+                name: "Day 4: Giant Squid".to_string(),
+                part: 2,
+                result: Err("line 3, column 1: \"oops\", again".to_string()),
+                parse_duration: Duration::from_nanos(1000),
+                duration: Duration::from_nanos(500),
+            },
+        ];
+        RunReport {
+            results,
+            wall_clock: Duration::from_nanos(2500),
+        }
+    }
+
+    #[test]
+    fn test_output_format_from_str() {
+        assert_eq!("json".parse(), Ok(OutputFormat::Json));
+        assert_eq!("csv".parse(), Ok(OutputFormat::Csv));
+        assert_eq!("text".parse(), Ok(OutputFormat::Text));
+        assert!("xml".parse::<OutputFormat>().is_err());
+    }
+
+    #[test]
+    fn test_render_text() {
+        assert_eq!(
+            render(OutputFormat::Text, &report()),
+            "~ Advent of Code 2021 ~\nDay 4: Giant Squid\n - Parse: (took 1.00µs)\n - Part 1: 4512 (took 1.50µs)\n - Part 2: error: line 3, column 1: \"oops\", again\nTook a total of 3.00µs (2.50µs wall clock)"
+        );
+    }
+
+    #[test]
+    fn test_render_json() {
+        assert_eq!(
+            render(OutputFormat::Json, &report()),
+            "{\n  \"results\": [\n    {\"day\": 4, \"name\": \"Day 4: Giant Squid\", \"part\": 1, \"answer\": 4512, \"answer_type\": \"integer\", \"parse_duration_ns\": 1000, \"duration_ns\": 1500, \"error\": null},\n    {\"day\": 4, \"name\": \"Day 4: Giant Squid\", \"part\": 2, \"answer\": null, \"answer_type\": null, \"parse_duration_ns\": 1000, \"duration_ns\": 500, \"error\": \"line 3, column 1: \\\"oops\\\", again\"}\n  ],\n  \"total_duration_ns\": 3000,\n  \"wall_clock_ns\": 2500\n}"
+        );
+    }
+
+    #[test]
+    fn test_render_csv() {
+        assert_eq!(
+            render(OutputFormat::Csv, &report()),
+            "day,name,part,answer,parse_duration_ns,duration_ns,error\n4,Day 4: Giant Squid,1,4512,1000,1500,\n4,Day 4: Giant Squid,2,,1000,500,\"line 3, column 1: \"\"oops\"\", again\""
+        );
+    }
+}
@@ -0,0 +1,280 @@
+use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    answer::Answer,
+    cli::Selection,
+    error::AocError,
+    input::InputSource,
+    problem::{ParsedInput, Solver},
+};
+
+/// The outcome of solving one part of one day.
+pub struct PartResult {
+    pub day: u8,
+    pub name: String,
+    pub part: u8,
+    pub result: Result<Answer, String>,
+    /// How long parsing the day's input took. Both parts share one parse, so
+    /// this is the same for every part of a day.
+    pub parse_duration: Duration,
+    pub duration: Duration,
+}
+
+/// Everything a run produced, in day and part order.
+pub struct RunReport {
+    pub results: Vec<PartResult>,
+    /// Elapsed time for the whole run, including reading inputs. With more
+    /// than one job this is less than the summed parse and solve times.
+    pub wall_clock: Duration,
+}
+
+/// Calls `work` on every item using up to `jobs` threads, returning the
+/// outputs in the same order as `items`.
+fn parallel_map<T: Sync, R: Send>(
+    items: &[T],
+    jobs: usize,
+    work: impl Fn(&T) -> R + Sync,
+) -> Vec<R> {
+    if jobs <= 1 || items.len() <= 1 {
+        return items.iter().map(work).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let outputs: Mutex<Vec<Option<R>>> = Mutex::new(items.iter().map(|_| None).collect());
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(items.len()) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(index) else {
+                    break;
+                };
+                let output = work(item);
+                outputs.lock().unwrap()[index] = Some(output);
+            });
+        }
+    });
+    outputs
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|output| output.expect("every item is processed"))
+        .collect()
+}
+
+/// Runs `work`, turning a panic in it into an error like any other, so a
+/// bug in one day doesn't stop the rest of the run.
+fn catch_panic<T>(work: impl FnOnce() -> Result<T, AocError>) -> Result<T, String> {
+    match panic::catch_unwind(AssertUnwindSafe(work)) {
+        Ok(result) => result.map_err(|error| error.to_string()),
+        Err(payload) => Err(format!("panicked: {}", panic_message(&*payload))),
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    match (
+        payload.downcast_ref::<&str>(),
+        payload.downcast_ref::<String>(),
+    ) {
+        (Some(message), _) => message,
+        (_, Some(message)) => message,
+        _ => "no message",
+    }
+}
+
+/// Solves the selected days and parts, using up to `jobs` threads. Each
+/// day's input is read and parsed once, then the parts of every day are
+/// solved independently of each other. Results always come back in day and
+/// part order, and failures are recorded in them rather than stopping the
+/// run, even when a day panics.
+pub fn run(
+    problems: &[Box<dyn Solver>],
+    selection: &Selection,
+    source: &InputSource,
+    jobs: usize,
+) -> RunReport {
+    let start = Instant::now();
+    let inputs: Vec<(&dyn Solver, &InputSource)> = problems
+        .iter()
+        .map(|problem| problem.as_ref())
+        .filter(|problem| selection.includes_day(problem.day()))
+        .map(|problem| (problem, source))
+        .collect();
+    let results = run_each(&inputs, selection, jobs);
+    RunReport {
+        results: results.into_iter().flatten().collect(),
+        wall_clock: start.elapsed(),
+    }
+}
+
+/// Like `run`, but for a list of days that each read their own input, where
+/// a day can appear more than once. All the inputs are parsed at once, then
+/// all their selected parts are solved at once, on up to `jobs` threads.
+/// Returns each input's results, in the same order as `inputs`.
+pub fn run_each(
+    inputs: &[(&dyn Solver, &InputSource)],
+    selection: &Selection,
+    jobs: usize,
+) -> Vec<Vec<PartResult>> {
+    let parsed: Vec<(Result<ParsedInput, String>, Duration)> =
+        parallel_map(inputs, jobs, |(problem, source)| {
+            let mut parse_duration = Duration::ZERO;
+            let parsed = catch_panic(|| {
+                let input = source.load(*problem)?;
+                let start = Instant::now();
+                let parsed = problem.parse_input(&input);
+                parse_duration = start.elapsed();
+                parsed
+            });
+            (parsed, parse_duration)
+        });
+
+    let tasks: Vec<(usize, u8)> = (0..inputs.len())
+        .flat_map(|index| [(index, 1), (index, 2)])
+        .filter(|(_, part)| selection.includes_part(*part))
+        .collect();
+    let results = parallel_map(&tasks, jobs, |&(index, part)| {
+        let problem = inputs[index].0;
+        let (parsed, parse_duration) = &parsed[index];
+        let (result, duration) = match parsed {
+            Ok(parsed) => {
+                let start = Instant::now();
+                let result = catch_panic(|| problem.solve_part(parsed, part));
+                (result, start.elapsed())
+            }
+            Err(error) => (Err(error.clone()), Duration::ZERO),
+        };
+        PartResult {
+            day: problem.day(),
+            name: problem.name().to_string(),
+            part,
+            result,
+            parse_duration: *parse_duration,
+            duration,
+        }
+    });
+
+    let mut grouped: Vec<Vec<PartResult>> = inputs.iter().map(|_| Vec::new()).collect();
+    for (&(index, _), result) in tasks.iter().zip(results) {
+        grouped[index].push(result);
+    }
+    grouped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{problem::Problem, problem_02::Problem02};
+
+    /// A day whose first part panics.
+    struct Panicking;
+
+    impl Problem for Panicking {
+        type Parsed = ();
+
+        fn name(&self) -> &str {
+            "Day 1: Panicking"
+        }
+
+        fn day(&self) -> u8 {
+            1
+        }
+
+        fn default_input(&self) -> Result<String, AocError> {
+            Ok(String::new())
+        }
+
+        fn parse(&self, _input: &str) -> Result<(), AocError> {
+            Ok(())
+        }
+
+        fn part1(&self, _parsed: &()) -> Result<Answer, AocError> {
+            panic!("part 1 is broken")
+        }
+
+        fn part2(&self, _parsed: &()) -> Result<Answer, AocError> {
+            Ok(2i64.into())
+        }
+    }
+
+    #[test]
+    fn test_parallel_map_keeps_order() {
+        let items: Vec<u64> = (0..50).collect();
+        for jobs in [1, 4, 100] {
+            assert_eq!(
+                parallel_map(&items, jobs, |item| item * item),
+                items.iter().map(|item| item * item).collect::<Vec<u64>>()
+            );
+        }
+    }
+
+    #[test]
+    fn test_run_each_groups_results_by_input() {
+        let problem = Problem02::new();
+        let example = InputSource::File("inputs/problem_02_example.txt".into());
+        let inputs: Vec<(&dyn Solver, &InputSource)> = vec![
+            (&problem, &example),
+            (&problem, &InputSource::Bundled),
+            (&problem, &example),
+        ];
+        let answers = |selection: &Selection| -> Vec<Vec<String>> {
+            run_each(&inputs, selection, 4)
+                .into_iter()
+                .map(|results| {
+                    results
+                        .into_iter()
+                        .map(|result| result.result.unwrap().to_string())
+                        .collect()
+                })
+                .collect()
+        };
+        assert_eq!(
+            answers(&Selection::default()),
+            vec![
+                vec!["150", "900"],
+                vec!["1693300", "1857958050"],
+                vec!["150", "900"]
+            ]
+        );
+        let part2 = Selection {
+            days: Vec::new(),
+            part: Some(2),
+        };
+        assert_eq!(
+            answers(&part2),
+            vec![vec!["900"], vec!["1857958050"], vec!["900"]]
+        );
+    }
+
+    #[test]
+    fn test_run_each_reports_panics() {
+        let problem = Panicking;
+        let source = InputSource::Bundled;
+        let results = run_each(&[(&problem, &source)], &Selection::default(), 1);
+        let results: Vec<Result<String, String>> = results[0]
+            .iter()
+            .map(|result| {
+                result
+                    .result
+                    .as_ref()
+                    .map(|answer| answer.to_string())
+                    .map_err(String::clone)
+            })
+            .collect();
+        assert_eq!(
+            results,
+            vec![
+                Err("panicked: part 1 is broken".to_string()),
+                Ok("2".to_string())
+            ]
+        );
+    }
+}
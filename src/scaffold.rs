@@ -0,0 +1,298 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The skeleton of a new day's module, in the shape every other day has:
+/// a `ProblemXX` with `new`, `solve_actual` and `solve_actual_part2`, its
+/// `Problem` impl, and tests against the example. Its number and title
+/// come from its entry in the `days!` list.
+pub fn module_source(day: u8) -> String {
+    format!(
+        r#"use crate::{{answer::Answer, error::AocError, problem::Problem}};
+
+#[derive(Default)]
+pub struct Problem{day:02} {{}}
+
+impl Problem{day:02} {{
+    pub fn new() -> Problem{day:02} {{
+        Problem{day:02} {{}}
+    }}
+
+    pub fn solve_actual(&self, _lines: &[String]) -> i64 {{
+        // TODO: solve part 1.
+        0
+    }}
+
+    pub fn solve_actual_part2(&self, _lines: &[String]) -> i64 {{
+        // TODO: solve part 2.
+        0
+    }}
+}}
+
+impl Problem for Problem{day:02} {{
+    type Parsed = Vec<String>;
+
+    fn name(&self) -> &str {{
+        Self::NAME
+    }}
+
+    fn day(&self) -> u8 {{
+        Self::DAY
+    }}
+
+    fn default_input(&self) -> Result<String, AocError> {{
+        get_input!("./inputs/problem_{day:02}.txt")
+    }}
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, AocError> {{
+        Ok(input.lines().map(str::to_string).collect())
+    }}
+
+    fn part1(&self, lines: &Self::Parsed) -> Result<Answer, AocError> {{
+        Ok(self.solve_actual(lines).into())
+    }}
+
+    fn part2(&self, lines: &Self::Parsed) -> Result<Answer, AocError> {{
+        Ok(self.solve_actual_part2(lines).into())
+    }}
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    #[test]
+    fn test_solve_actual_from_example() {{
+        let problem = Problem{day:02}::new();
+        let input = get_input!("./inputs/problem_{day:02}_example.txt").unwrap();
+        let lines = problem.parse(&input).unwrap();
+        // TODO: the example's answer.
+        assert_eq!(problem.solve_actual(&lines), 0);
+    }}
+
+    #[test]
+    fn test_solve_actual_part2_from_example() {{
+        let problem = Problem{day:02}::new();
+        let input = get_input!("./inputs/problem_{day:02}_example.txt").unwrap();
+        let lines = problem.parse(&input).unwrap();
+        // TODO: the example's answer.
+        assert_eq!(problem.solve_actual_part2(&lines), 0);
+    }}
+}}
+"#,
+        day = day,
+    )
+}
+
+/// Adds `day` and its `title` to the `days!` list in `lib`, the source of
+/// `lib.rs`. The list has to stay in order from day 1 with none missing, so
+/// only the next day or one filling a gap can be added.
+pub fn register(lib: &str, day: u8, title: &str) -> Result<String, String> {
+    let start = lib
+        .find("days! {\n")
+        .ok_or("couldn't find the days! list in lib.rs")?
+        + "days! {\n".len();
+    let length = lib[start..]
+        .find("}\n")
+        .ok_or("couldn't find the end of the days! list in lib.rs")?;
+
+    let mut entries: Vec<(u8, String)> = Vec::new();
+    for line in lib[start..start + length].lines() {
+        let registered = line
+            .split_once("=>")
+            .and_then(|(day, _)| day.trim().parse().ok())
+            .ok_or_else(|| format!("couldn't read '{}' in the days! list", line.trim()))?;
+        entries.push((registered, line.to_string()));
+    }
+    if entries.iter().any(|(registered, _)| *registered == day) {
+        return Err(format!("day {} is already registered", day));
+    }
+    entries.push((
+        day,
+        format!(
+            "    {} => problem_{:02}::Problem{:02} {:?},",
+            day, day, day, title
+        ),
+    ));
+    entries.sort_by_key(|(registered, _)| *registered);
+    if entries
+        .iter()
+        .zip(1..)
+        .any(|((registered, _), n)| *registered != n)
+    {
+        return Err(format!(
+            "days must be added in order, and day {} would leave a gap",
+            day
+        ));
+    }
+
+    let lines: Vec<String> = entries.into_iter().map(|(_, line)| line).collect();
+    Ok(format!(
+        "{}{}\n{}",
+        &lib[..start],
+        lines.join("\n"),
+        &lib[start + length..]
+    ))
+}
+
+/// Creates a new day in the crate at `root`: its module, a placeholder
+/// input and example, the example's (empty) answers, and its entry in the
+/// `days!` list. Nothing is written if any of the files already exist, and
+/// if a write fails the files already written are removed, so the day is
+/// added whole or not at all. Returns the files it wrote.
+pub fn scaffold(root: &Path, day: u8, title: &str) -> Result<Vec<PathBuf>, String> {
+    let lib_path = root.join("src/lib.rs");
+    let lib = fs::read_to_string(&lib_path).map_err(|error| {
+        format!(
+            "can't read {}, run this from the crate's root: {}",
+            lib_path.display(),
+            error
+        )
+    })?;
+    let lib = register(&lib, day, title)?;
+
+    let new_files = [
+        (
+            root.join(format!("src/problem_{:02}.rs", day)),
+            module_source(day),
+        ),
+        (
+            root.join(format!("inputs/problem_{:02}.txt", day)),
+            String::new(),
+        ),
+        (
+            root.join(format!("inputs/problem_{:02}_example.txt", day)),
+            String::new(),
+        ),
+        (
+            root.join(format!("inputs/problem_{:02}_example.answers", day)),
+            "# part1 = ...\n# part2 = ...\n".to_string(),
+        ),
+    ];
+    if let Some((path, _)) = new_files.iter().find(|(path, _)| path.exists()) {
+        return Err(format!("{} already exists", path.display()));
+    }
+
+    // lib.rs goes last, so it's only changed once the day's files are there.
+    let mut files = Vec::from(new_files);
+    files.push((lib_path, lib));
+    for (index, (path, contents)) in files.iter().enumerate() {
+        if let Err(error) = fs::write(path, contents) {
+            for (written, _) in &files[..index] {
+                let _ = fs::remove_file(written);
+            }
+            return Err(format!("can't write {}: {}", path.display(), error));
+        }
+    }
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIB: &str = "pub mod util;\n\ndays! {\n    1 => problem_01::Problem01 \"Sonar Sweep\",\n    3 => problem_03::Problem03 \"Binary Diagnostic\",\n}\n\nconst fn check_days() {}\n";
+
+    #[test]
+    fn test_register_keeps_days_in_order() {
+        assert_eq!(
+            register(LIB, 2, "Dive!").unwrap(),
+            "pub mod util;\n\ndays! {\n    1 => problem_01::Problem01 \"Sonar Sweep\",\n    2 => problem_02::Problem02 \"Dive!\",\n    3 => problem_03::Problem03 \"Binary Diagnostic\",\n}\n\nconst fn check_days() {}\n"
+        );
+        assert_eq!(
+            register(LIB, 3, "Binary Diagnostic"),
+            Err("day 3 is already registered".to_string())
+        );
+        assert!(register(LIB, 5, "Hydrothermal Venture").is_err());
+        assert!(register("pub mod util;\n", 1, "Sonar Sweep").is_err());
+    }
+
+    #[test]
+    fn test_register_escapes_the_title() {
+        let lib = register(LIB, 2, "The \"Treachery\" of Whales").unwrap();
+        assert!(lib.contains(r#"    2 => problem_02::Problem02 "The \"Treachery\" of Whales","#));
+    }
+
+    #[test]
+    fn test_module_source() {
+        let source = module_source(7);
+        assert!(source.contains("pub struct Problem07 {}"));
+        assert!(source.contains("Self::NAME"));
+        assert!(source.contains("get_input!(\"./inputs/problem_07.txt\")"));
+        assert!(source.contains("get_input!(\"./inputs/problem_07_example.txt\")"));
+    }
+
+    /// A copy of this crate's `lib.rs` in a new directory under the system's
+    /// temporary directory, with only days 1 to 16 registered.
+    fn shortened_crate(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("aoc-2021-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src")).unwrap();
+        let lib =
+            fs::read_to_string(Path::new(env!("CARGO_MANIFEST_DIR")).join("src/lib.rs")).unwrap();
+        let lib: Vec<&str> = lib
+            .lines()
+            .filter(|line| !(17..=25).any(|day| line.starts_with(&format!("    {} => ", day))))
+            .collect();
+        fs::write(root.join("src/lib.rs"), lib.join("\n") + "\n").unwrap();
+        root
+    }
+
+    #[test]
+    fn test_scaffold_writes_a_new_day() {
+        let root = shortened_crate("scaffold");
+        fs::create_dir(root.join("inputs")).unwrap();
+        let lib = fs::read_to_string(root.join("src/lib.rs")).unwrap();
+
+        let written = scaffold(&root, 17, "Trick Shot").unwrap();
+        assert_eq!(
+            written,
+            vec![
+                root.join("src/problem_17.rs"),
+                root.join("inputs/problem_17.txt"),
+                root.join("inputs/problem_17_example.txt"),
+                root.join("inputs/problem_17_example.answers"),
+                root.join("src/lib.rs"),
+            ]
+        );
+        let read = |path: &str| fs::read_to_string(root.join(path)).unwrap();
+        assert_eq!(read("src/problem_17.rs"), module_source(17));
+        assert_eq!(read("inputs/problem_17.txt"), "");
+        assert_eq!(
+            read("src/lib.rs"),
+            register(&lib, 17, "Trick Shot").unwrap()
+        );
+        assert!(read("src/lib.rs").contains(
+            "    16 => problem_16::Problem16 \"Packet Decoder\",\n    17 => problem_17::Problem17 \"Trick Shot\",\n}"
+        ));
+        assert_eq!(
+            scaffold(&root, 17, "Trick Shot"),
+            Err("day 17 is already registered".to_string())
+        );
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_scaffold_removes_its_files_when_a_write_fails() {
+        // With no inputs directory, writing the day's input fails after its
+        // module has been written.
+        let root = shortened_crate("scaffold-failure");
+        let lib = fs::read_to_string(root.join("src/lib.rs")).unwrap();
+
+        let error = scaffold(&root, 17, "Trick Shot").unwrap_err();
+        assert!(error.contains("problem_17.txt"), "{}", error);
+        assert!(!root.join("src/problem_17.rs").exists());
+        assert_eq!(fs::read_to_string(root.join("src/lib.rs")).unwrap(), lib);
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_scaffold_refuses_a_registered_day() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        assert_eq!(
+            scaffold(root, 17, "Trick Shot"),
+            Err("day 17 is already registered".to_string())
+        );
+    }
+}
@@ -0,0 +1,279 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+/// The cheapest way found from a start node to a goal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N> {
+    pub cost: i64,
+    /// Every node from the start to the goal, with the cost of getting there.
+    pub nodes: Vec<(N, i64)>,
+}
+
+/// Every node reachable from `start`, in breadth-first order, `start`
+/// included.
+pub fn reachable<N, I>(start: N, mut neighbors: impl FnMut(&N) -> I) -> Vec<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::from([start.clone()]);
+    let mut order = vec![start];
+    let mut next = 0;
+    while let Some(node) = order.get(next) {
+        for neighbor in neighbors(node) {
+            if seen.insert(neighbor.clone()) {
+                order.push(neighbor);
+            }
+        }
+        next += 1;
+    }
+    order
+}
+
+/// Breadth-first search for the path with the fewest steps from `start` to
+/// a node where `is_goal` holds. Every step costs 1.
+pub fn bfs<N, I>(
+    start: N,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut previous: HashMap<N, Option<N>> = HashMap::from([(start.clone(), None)]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        if is_goal(&node) {
+            let mut nodes = vec![node];
+            while let Some(Some(before)) = previous.get(&nodes[nodes.len() - 1]) {
+                nodes.push(before.clone());
+            }
+            nodes.reverse();
+            return Some(Path {
+                cost: nodes.len() as i64 - 1,
+                nodes: nodes.into_iter().zip(0..).collect(),
+            });
+        }
+        for neighbor in neighbors(&node) {
+            if !previous.contains_key(&neighbor) {
+                previous.insert(neighbor.clone(), Some(node.clone()));
+                queue.push_back(neighbor);
+            }
+        }
+    }
+    None
+}
+
+/// Dijkstra's algorithm: the cheapest path from `start` to a node where
+/// `is_goal` holds. `neighbors` gives each node's neighbours with the cost
+/// of stepping to them, which must not be negative.
+pub fn dijkstra<N, I>(
+    start: N,
+    neighbors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = (N, i64)>,
+{
+    astar(start, neighbors, is_goal, |_| 0)
+}
+
+/// Like `dijkstra`, for graphs whose `count` nodes `index` numbers from 0,
+/// such as the cells of a grid. Nodes are looked up in a table by their
+/// number rather than hashed, which is much quicker on large graphs.
+pub fn dijkstra_indexed<N, I>(
+    start: N,
+    count: usize,
+    index: impl Fn(&N) -> usize,
+    neighbors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N>>
+where
+    N: Clone,
+    I: IntoIterator<Item = (N, i64)>,
+{
+    let ids = IndexedIds {
+        ids: vec![None; count],
+        index,
+    };
+    search(start, ids, neighbors, is_goal, |_| 0)
+}
+
+/// A* search: like `dijkstra`, but explores nodes in order of their cost so
+/// far plus `heuristic`'s estimate of the cost left. The path is only the
+/// cheapest if the estimate never exceeds the real cost.
+pub fn astar<N, I>(
+    start: N,
+    neighbors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+    heuristic: impl FnMut(&N) -> i64,
+) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = (N, i64)>,
+{
+    search(start, HashMap::new(), neighbors, is_goal, heuristic)
+}
+
+/// Finds the id `search` gave a node, if it has seen it.
+trait NodeIds<N> {
+    fn get(&self, node: &N) -> Option<usize>;
+    fn insert(&mut self, node: &N, id: usize);
+}
+
+impl<N: Clone + Eq + Hash> NodeIds<N> for HashMap<N, usize> {
+    fn get(&self, node: &N) -> Option<usize> {
+        HashMap::get(self, node).copied()
+    }
+
+    fn insert(&mut self, node: &N, id: usize) {
+        HashMap::insert(self, node.clone(), id);
+    }
+}
+
+/// Ids kept in a table, for nodes that `index` numbers densely.
+struct IndexedIds<F> {
+    ids: Vec<Option<usize>>,
+    index: F,
+}
+
+impl<N, F: Fn(&N) -> usize> NodeIds<N> for IndexedIds<F> {
+    fn get(&self, node: &N) -> Option<usize> {
+        self.ids[(self.index)(node)]
+    }
+
+    fn insert(&mut self, node: &N, id: usize) {
+        self.ids[(self.index)(node)] = Some(id);
+    }
+}
+
+/// The search behind `astar` and the `dijkstra` variants, with `ids`
+/// deciding how nodes are looked up.
+fn search<N, I>(
+    start: N,
+    mut ids: impl NodeIds<N>,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+    mut heuristic: impl FnMut(&N) -> i64,
+) -> Option<Path<N>>
+where
+    N: Clone,
+    I: IntoIterator<Item = (N, i64)>,
+{
+    // Nodes are kept in `nodes` and referred to by index everywhere else,
+    // so they don't need to be `Ord` to go in the heap.
+    ids.insert(&start, 0);
+    let mut nodes = vec![start];
+    // The cheapest known cost of each node, and the node it's reached from.
+    let mut best: Vec<(i64, Option<usize>)> = vec![(0, None)];
+    let mut queue = BinaryHeap::from([Reverse((heuristic(&nodes[0]), 0, 0))]);
+
+    while let Some(Reverse((_, cost, id))) = queue.pop() {
+        if cost > best[id].0 {
+            continue;
+        }
+        if is_goal(&nodes[id]) {
+            let mut path = vec![id];
+            while let (_, Some(before)) = best[path[path.len() - 1]] {
+                path.push(before);
+            }
+            return Some(Path {
+                cost,
+                nodes: path
+                    .into_iter()
+                    .rev()
+                    .map(|id| (nodes[id].clone(), best[id].0))
+                    .collect(),
+            });
+        }
+        for (neighbor, step) in neighbors(&nodes[id]) {
+            let next_cost = cost + step;
+            let next_id = match ids.get(&neighbor) {
+                Some(next_id) if best[next_id].0 <= next_cost => continue,
+                Some(next_id) => next_id,
+                None => {
+                    ids.insert(&neighbor, nodes.len());
+                    nodes.push(neighbor);
+                    best.push((i64::MAX, None));
+                    nodes.len() - 1
+                }
+            };
+            best[next_id] = (next_cost, Some(id));
+            let estimate = next_cost + heuristic(&nodes[next_id]);
+            queue.push(Reverse((estimate, next_cost, next_id)));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A small weighted graph where the direct edge is not the cheapest:
+    // 0 -> 3 costs 10, but 0 -> 1 -> 2 -> 3 costs 6.
+    fn edges(node: &u8) -> Vec<(u8, i64)> {
+        match node {
+            0 => vec![(1, 1), (3, 10)],
+            1 => vec![(2, 2), (0, 1)],
+            2 => vec![(3, 3)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_dijkstra_and_astar_find_cheapest_path() {
+        let expected = Path {
+            cost: 6,
+            nodes: vec![(0, 0), (1, 1), (2, 3), (3, 6)],
+        };
+        assert_eq!(
+            dijkstra(0, edges, |node| *node == 3),
+            Some(expected.clone())
+        );
+        let heuristic = |node: &u8| 3 - *node as i64;
+        assert_eq!(
+            astar(0, edges, |node| *node == 3, heuristic),
+            Some(expected)
+        );
+        assert_eq!(dijkstra(2, edges, |node| *node == 0), None);
+        let index = |node: &u8| *node as usize;
+        assert_eq!(
+            dijkstra_indexed(0, 4, index, edges, |node| *node == 3),
+            dijkstra(0, edges, |node| *node == 3)
+        );
+        assert_eq!(
+            dijkstra_indexed(2, 4, index, edges, |node| *node == 0),
+            None
+        );
+    }
+
+    #[test]
+    fn test_bfs_counts_steps() {
+        let path = bfs(
+            0u8,
+            |node| edges(node).into_iter().map(|(n, _)| n),
+            |node| *node == 3,
+        );
+        assert_eq!(
+            path,
+            Some(Path {
+                cost: 1,
+                nodes: vec![(0, 0), (3, 1)],
+            })
+        );
+        assert_eq!(bfs(3u8, |_| Vec::new(), |node| *node == 3).unwrap().cost, 0);
+    }
+
+    #[test]
+    fn test_reachable() {
+        let reached = reachable(1u8, |node| edges(node).into_iter().map(|(n, _)| n));
+        assert_eq!(reached, vec![1, 2, 0, 3]);
+        assert_eq!(reachable(3u8, |_| Vec::new()), vec![3]);
+    }
+}
@@ -1,20 +1,109 @@
-#[cfg(debug_assertions)]
-use std::{fs::File, io::Read};
-
-#[cfg(debug_assertions)]
-pub fn read_file(path: &str) -> Option<String> {
-    let file: Option<File> = match File::open(path) {
-        Ok(valid_file) => Some(valid_file),
-        Err(_e) => None,
-    };
-
-    if file.is_none() {
-        return None;
+use std::{fmt::Display, fs, path::Path, str::FromStr};
+
+use crate::error::AocError;
+
+pub fn read_file(path: impl AsRef<Path>) -> Result<String, AocError> {
+    let path = path.as_ref();
+    fs::read_to_string(path).map_err(|e| AocError::from_io(path, e))
+}
+
+/// Parses `token`, which must be a slice of `line`, reporting the token's
+/// position in the input if it isn't valid. `line_index` is 0-indexed, as
+/// given by `enumerate()`.
+pub fn parse_token<T>(line_index: usize, line: &str, token: &str) -> Result<T, AocError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    token.trim().parse::<T>().map_err(|e| {
+        AocError::parse(
+            line_index + 1,
+            column_of(line, token),
+            format!("'{}': {}", token, e),
+        )
+    })
+}
+
+/// Returns the 1-indexed column at which `token`, a slice of `line`, starts.
+pub fn column_of(line: &str, token: &str) -> usize {
+    let offset = (token.as_ptr() as usize).saturating_sub(line.as_ptr() as usize);
+    line.get(..offset.min(line.len()))
+        .map_or(1, |prefix| prefix.chars().count() + 1)
+}
+
+/// Parses a line of `#` (true) and `.` (false) pixels.
+pub fn parse_pixels(line_index: usize, line: &str) -> Result<Vec<bool>, AocError> {
+    line.chars()
+        .enumerate()
+        .map(|(column, c)| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(AocError::parse(
+                line_index + 1,
+                column + 1,
+                format!("expected '#' or '.', found '{}'", c),
+            )),
+        })
+        .collect()
+}
+
+/// Quotes and escapes `value` as a JSON string.
+pub fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_token() {
+        let line = "12,x4";
+        let (first, second) = line.split_once(',').unwrap();
+        assert_eq!(parse_token::<i64>(0, line, first).unwrap(), 12);
+        match parse_token::<i64>(4, line, second) {
+            Err(AocError::Parse { line, column, .. }) => assert_eq!((line, column), (5, 4)),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_pixels() {
+        assert_eq!(
+            parse_pixels(0, "#..#").unwrap(),
+            vec![true, false, false, true]
+        );
+        match parse_pixels(2, "#.o") {
+            Err(AocError::Parse { line, column, .. }) => assert_eq!((line, column), (3, 3)),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!(json_string("plain"), "\"plain\"");
+        assert_eq!(json_string("a\"b\\c\nd\u{1}"), "\"a\\\"b\\\\c\\nd\\u0001\"");
     }
 
-    let mut input = String::new();
-    match file.unwrap().read_to_string(&mut input) {
-        Ok(_valid) => Some(input),
-        Err(_e) => None,
+    #[test]
+    fn test_read_file_missing() {
+        assert!(matches!(
+            read_file("./inputs/problem_00.txt"),
+            Err(AocError::MissingInput { .. })
+        ));
     }
 }
@@ -0,0 +1,208 @@
+use std::path::PathBuf;
+
+use crate::{
+    answers::KnownAnswers,
+    cli::Selection,
+    input::InputSource,
+    problem::Solver,
+    runner::{self, PartResult},
+};
+
+/// How a part's answer compares to the known one.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: String,
+    },
+    /// There's no known answer to compare against.
+    Unknown,
+}
+
+/// One part of one input, checked.
+pub struct Check {
+    /// The input the part was solved from. `None` for the bundled input.
+    pub input: Option<PathBuf>,
+    pub result: PartResult,
+    pub verdict: Verdict,
+}
+
+impl Check {
+    fn new(input: Option<PathBuf>, result: PartResult, expected: Option<&String>) -> Check {
+        let verdict = match (expected, &result.result) {
+            (None, _) => Verdict::Unknown,
+            (Some(expected), Ok(answer)) if answer.to_string() == *expected => Verdict::Pass,
+            (Some(expected), _) => Verdict::Fail {
+                expected: expected.clone(),
+            },
+        };
+        Check {
+            input,
+            result,
+            verdict,
+        }
+    }
+}
+
+/// Solves every selected day and part from each input the answers list for
+/// it, and compares the results. Selected days without any known answers
+/// are still solved from their bundled input, and come back `Unknown`.
+/// Every input is checked at once, on up to `jobs` threads.
+pub fn verify(
+    problems: &[Box<dyn Solver>],
+    selection: &Selection,
+    answers: &[KnownAnswers],
+    jobs: usize,
+) -> Vec<Check> {
+    // Every input to solve, with the answers known for it.
+    let mut checked: Vec<(&dyn Solver, InputSource, Option<&KnownAnswers>)> = Vec::new();
+    for problem in problems {
+        if !selection.includes_day(problem.day()) {
+            continue;
+        }
+        let known: Vec<&KnownAnswers> = answers
+            .iter()
+            .filter(|known| known.day == problem.day())
+            .collect();
+        if known.is_empty() {
+            checked.push((problem.as_ref(), InputSource::Bundled, None));
+        }
+        for known in known {
+            let source = InputSource::File(known.input.clone());
+            checked.push((problem.as_ref(), source, Some(known)));
+        }
+    }
+
+    let inputs: Vec<(&dyn Solver, &InputSource)> = checked
+        .iter()
+        .map(|(problem, source, _)| (*problem, source))
+        .collect();
+    let results = runner::run_each(&inputs, selection, jobs);
+    checked
+        .iter()
+        .zip(results)
+        .flat_map(|((_, _, known), results)| {
+            results.into_iter().map(move |result| match known {
+                Some(known) => {
+                    let expected = known.parts[result.part as usize - 1].as_ref();
+                    Check::new(Some(known.input.clone()), result, expected)
+                }
+                None => Check::new(None, result, None),
+            })
+        })
+        .collect()
+}
+
+/// Lists every check as PASS, FAIL or UNKNOWN, showing how each failure
+/// differs from the known answer, and ends with a count of each.
+pub fn render(checks: &[Check]) -> String {
+    let mut output = Vec::new();
+    let (mut passed, mut failed, mut unknown) = (0, 0, 0);
+    for check in checks {
+        let result = &check.result;
+        let input = check
+            .input
+            .as_ref()
+            .map_or("bundled input".to_string(), |path| {
+                path.display().to_string()
+            });
+        let verdict = match check.verdict {
+            Verdict::Pass => "PASS",
+            Verdict::Fail { .. } => "FAIL",
+            Verdict::Unknown => "UNKNOWN",
+        };
+        output.push(format!(
+            "{:<7} Day {:>2} part {} ({})",
+            verdict, result.day, result.part, input
+        ));
+        let actual = match &result.result {
+            Ok(answer) => answer.to_string(),
+            Err(error) => format!("error: {}", error),
+        };
+        match &check.verdict {
+            Verdict::Pass => passed += 1,
+            Verdict::Fail { expected } => {
+                failed += 1;
+                output.extend(diff(expected, &actual));
+            }
+            Verdict::Unknown => {
+                unknown += 1;
+                output.push(format!(
+                    "        got {}",
+                    actual.replace('\n', "\n            ")
+                ));
+            }
+        }
+    }
+    output.push(format!(
+        "{} passed, {} failed, {} unknown",
+        passed, failed, unknown
+    ));
+    output.join("\n")
+}
+
+/// The lines that differ between two answers, prefixed `-` for the expected
+/// answer and `+` for the actual one. Single line answers differ in full.
+fn diff(expected: &str, actual: &str) -> Vec<String> {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+    let mut lines = Vec::new();
+    for index in 0..expected.len().max(actual.len()) {
+        let (old, new) = (expected.get(index), actual.get(index));
+        if old == new {
+            continue;
+        }
+        if let Some(old) = old {
+            lines.push(format!("        - {}", old));
+        }
+        if let Some(new) = new {
+            lines.push(format!("        + {}", new));
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{answers, problem_02::Problem02};
+
+    #[test]
+    fn test_verify_reports_each_verdict() {
+        let problems: Vec<Box<dyn Solver>> = vec![Box::new(Problem02::new())];
+        let known = answers::parse(
+            "[[answer]]\nday = 2\ninput = \"inputs/problem_02_example.txt\"\npart1 = 150\npart2 = 901",
+        )
+        .unwrap();
+        let checks = verify(&problems, &Selection::default(), &known, 1);
+        let verdicts: Vec<&Verdict> = checks.iter().map(|check| &check.verdict).collect();
+        assert_eq!(
+            verdicts,
+            vec![
+                &Verdict::Pass,
+                &Verdict::Fail {
+                    expected: "901".to_string()
+                }
+            ]
+        );
+
+        let rendered = render(&checks);
+        assert!(rendered.contains("PASS    Day  2 part 1 (inputs/problem_02_example.txt)"));
+        assert!(rendered.contains("        - 901\n        + 900"));
+        assert!(rendered.ends_with("1 passed, 1 failed, 0 unknown"));
+
+        let checks = verify(&problems, &Selection::default(), &[], 1);
+        assert!(checks
+            .iter()
+            .all(|check| check.verdict == Verdict::Unknown && check.input.is_none()));
+    }
+
+    #[test]
+    fn test_diff_only_shows_changed_lines() {
+        assert_eq!(diff("1", "2"), vec!["        - 1", "        + 2"]);
+        assert_eq!(
+            diff("#.#\n.#.\n###", "#.#\n##.\n###\n..."),
+            vec!["        - .#.", "        + ##.", "        + ..."]
+        );
+    }
+}
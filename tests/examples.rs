@@ -0,0 +1,236 @@
+//! Solves every example in `inputs/` and checks it against the answers in the
+//! sidecar next to it, so adding an example takes two files and no Rust:
+//! `problem_XX_example[_NN].txt` and `problem_XX_example[_NN].answers`. The
+//! sidecar holds `part1 = ...` and `part2 = ...` lines, like an entry in
+//! `answers.toml`; a part left out of it isn't checked.
+//!
+//! Runs without the libtest harness, so only a few of its arguments are
+//! understood: name filters, `--exact`, `--skip NAME` (or `--skip=NAME`),
+//! `--list` and `--quiet`. Any other flag is ignored, and one that takes a
+//! value must be given it as `--flag=value`, or the value is read as a
+//! filter.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+use advent_of_code_2021::{
+    answers,
+    input::InputSource,
+    problem::{ParsedInput, Solver},
+    problems,
+};
+
+/// One example input, paired with the day that solves it.
+struct Example {
+    day: u8,
+    /// The input's file name without `.txt`, e.g. `problem_16_example_01`.
+    name: String,
+    input: PathBuf,
+}
+
+impl Example {
+    fn sidecar(&self) -> PathBuf {
+        self.input.with_extension("answers")
+    }
+
+    fn test_name(&self, part: u8) -> String {
+        format!("{}::part{}", self.name, part)
+    }
+}
+
+/// The day an example input is for, if the file name is one. The day must be
+/// two digits and an `_NN` suffix is allowed, as in `problem_12_example_03`.
+fn example_day(file_name: &str) -> Option<u8> {
+    let rest = file_name.strip_prefix("problem_")?.strip_suffix(".txt")?;
+    let (day, rest) = rest.split_at_checked(2)?;
+    let suffix = rest.strip_prefix("_example")?;
+    if !suffix.is_empty() {
+        let number = suffix.strip_prefix('_')?;
+        if number.is_empty() || !number.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+    }
+    match day.bytes().all(|b| b.is_ascii_digit()) {
+        true => day.parse().ok(),
+        false => None,
+    }
+}
+
+fn discover(directory: &Path) -> Vec<Example> {
+    let entries = fs::read_dir(directory)
+        .unwrap_or_else(|error| panic!("can't read {}: {}", directory.display(), error));
+    let mut examples: Vec<Example> = entries
+        .map(|entry| entry.expect("can't read the inputs directory").path())
+        .filter_map(|input| {
+            let file_name = input.file_name()?.to_str()?;
+            Some(Example {
+                day: example_day(file_name)?,
+                name: file_name.strip_suffix(".txt")?.to_string(),
+                input,
+            })
+        })
+        .collect();
+    examples.sort_by(|a, b| a.name.cmp(&b.name));
+    examples
+}
+
+/// Checks a part's answer against the expected one.
+fn check(
+    problem: &dyn Solver,
+    parsed: &Result<ParsedInput, String>,
+    part: u8,
+    expected: &str,
+) -> Result<(), String> {
+    let parsed = parsed.as_ref().map_err(|error| error.clone())?;
+    match problem.solve_part(parsed, part) {
+        Ok(answer) if answer.to_string() == expected => Ok(()),
+        Ok(answer) => Err(format!("expected {}, got {}", expected, answer)),
+        Err(error) => Err(format!("expected {}, got error: {}", expected, error)),
+    }
+}
+
+/// Which tests to run, from the arguments `cargo test` passes along.
+#[derive(Default)]
+struct Options {
+    filters: Vec<String>,
+    skips: Vec<String>,
+    exact: bool,
+    list: bool,
+    quiet: bool,
+}
+
+impl Options {
+    fn parse(args: impl IntoIterator<Item = String>) -> Options {
+        let mut options = Options::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--exact" => options.exact = true,
+                "--list" => options.list = true,
+                "-q" | "--quiet" => options.quiet = true,
+                "--skip" => options.skips.extend(args.next()),
+                flag if flag.starts_with("--skip=") => {
+                    options.skips.push(flag["--skip=".len()..].to_string())
+                }
+                flag if flag.starts_with('-') => {}
+                _ => options.filters.push(arg),
+            }
+        }
+        options
+    }
+
+    fn matches(&self, name: &str, pattern: &str) -> bool {
+        match self.exact {
+            true => name == pattern,
+            false => name.contains(pattern),
+        }
+    }
+
+    fn selected(&self, name: &str) -> bool {
+        (self.filters.is_empty() || self.filters.iter().any(|filter| self.matches(name, filter)))
+            && !self.skips.iter().any(|skip| self.matches(name, skip))
+    }
+}
+
+fn main() {
+    let options = Options::parse(env::args().skip(1));
+    let (list, quiet) = (options.list, options.quiet);
+    let selected = |name: &str| options.selected(name);
+
+    let problems = problems();
+    let inputs = Path::new(env!("CARGO_MANIFEST_DIR")).join("inputs");
+    // Each test's name, and its outcome: `Err` with why it failed.
+    let mut outcomes: Vec<(String, Result<(), String>)> = Vec::new();
+    for example in discover(&inputs) {
+        let problem = problems.iter().find(|problem| problem.day() == example.day);
+        let loaded = match problem {
+            Some(problem) => fs::read_to_string(example.sidecar())
+                .map_err(|error| error.to_string())
+                .and_then(|text| answers::parse_parts(&text).map_err(|error| error.to_string()))
+                .map(|parts| (problem, parts))
+                .map_err(|error| format!("{}: {}", example.sidecar().display(), error)),
+            None => Err(format!("no day {} is registered", example.day)),
+        };
+        let (problem, parts) = match loaded {
+            Ok(loaded) => loaded,
+            Err(error) => {
+                if selected(&example.name) {
+                    outcomes.push((example.name.clone(), Err(error)));
+                }
+                continue;
+            }
+        };
+        let tests: Vec<(u8, String)> = (1..=2)
+            .zip(parts)
+            .filter_map(|(part, expected)| Some((part, expected?)))
+            .filter(|(part, _)| selected(&example.test_name(*part)))
+            .collect();
+        if tests.is_empty() {
+            continue;
+        }
+        if list {
+            outcomes.extend(
+                tests
+                    .iter()
+                    .map(|(part, _)| (example.test_name(*part), Ok(()))),
+            );
+            continue;
+        }
+        // Loaded the way `run --input` loads it, so trimmed the same way.
+        let parsed = InputSource::File(example.input.clone())
+            .load(problem.as_ref())
+            .and_then(|input| problem.parse_input(&input))
+            .map_err(|error| format!("parse failed: {}", error));
+        for (part, expected) in tests {
+            let outcome = check(problem.as_ref(), &parsed, part, &expected);
+            outcomes.push((example.test_name(part), outcome));
+        }
+    }
+
+    if list {
+        for (name, _) in &outcomes {
+            println!("{}: test", name);
+        }
+        return;
+    }
+
+    println!("\nrunning {} tests", outcomes.len());
+    for (name, outcome) in &outcomes {
+        let status = match outcome {
+            Ok(()) => "ok",
+            Err(_) => "FAILED",
+        };
+        match quiet {
+            true => print!("{}", if outcome.is_ok() { "." } else { "F" }),
+            false => println!("test {} ... {}", name, status),
+        }
+    }
+    if quiet {
+        println!();
+    }
+
+    let failures: Vec<&(String, Result<(), String>)> = outcomes
+        .iter()
+        .filter(|(_, outcome)| outcome.is_err())
+        .collect();
+    if !failures.is_empty() {
+        println!("\nfailures:");
+        for (name, outcome) in &failures {
+            if let Err(error) = outcome {
+                println!("    {}: {}", name, error);
+            }
+        }
+    }
+    println!(
+        "\ntest result: {}. {} passed; {} failed\n",
+        if failures.is_empty() { "ok" } else { "FAILED" },
+        outcomes.len() - failures.len(),
+        failures.len()
+    );
+    if !failures.is_empty() {
+        process::exit(101);
+    }
+}